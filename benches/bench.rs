@@ -24,5 +24,71 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn transform_block_benchmark(c: &mut Criterion) {
+    let tokens = quote! {
+        <div>
+            {%}{a}{b}{c}{d}{e}{f}{g}{h}{i}{j}
+            {a}{b}{c}{d}{e}{f}{g}{h}{i}{j}
+            {a}{b}{c}{d}{e}{f}{g}{h}{i}{j}
+            {a}{b}{c}{d}{e}{f}{g}{h}{i}{j}
+        </div>
+    };
+
+    let config = || {
+        syn_rsx::ParserConfig::new().transform_block(|input| {
+            if input.peek(syn::Token![%]) {
+                input.parse::<syn::Token![%]>()?;
+                Ok(Some(quote! { "percent" }))
+            } else {
+                Ok(None)
+            }
+        })
+    };
+
+    c.bench_function("syn_rsx::parse2 transform_block", |b| {
+        b.iter(|| syn_rsx::parse2_with_config(tokens.clone(), config()))
+    });
+
+    c.bench_function("syn_rsx::parse2 transform_block_if", |b| {
+        b.iter(|| {
+            syn_rsx::parse2_with_config(
+                tokens.clone(),
+                config().transform_block_if(|input| input.peek(syn::Token![%])),
+            )
+        })
+    });
+}
+
+fn repeated_names_benchmark(c: &mut Criterion) {
+    let row = quote! {
+        <li class="nav-item">
+            <a class="nav-link" href="/learn">"Learn"</a>
+            <ul><li><a href="/tools">"Tools"</a></li><li><a href="/docs">"Docs"</a></li></ul>
+        </li>
+    };
+    let rows = (0..50).map(|_| row.clone());
+    let tokens = quote! { <nav><ul>#(#rows)*</ul></nav> };
+
+    let config = || {
+        syn_rsx::ParserConfig::new()
+            .element_classification(
+                vec![("br", syn_rsx::ElementClassification::Void)]
+                    .into_iter()
+                    .collect(),
+            )
+            .template_elements(vec!["template"].into_iter().collect())
+            .foreign_content_elements(vec!["svg"].into_iter().collect())
+    };
+
+    c.bench_function("syn_rsx::parse2 repeated names", |b| {
+        b.iter(|| syn_rsx::parse2_with_config(tokens.clone(), config()))
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    transform_block_benchmark,
+    repeated_names_benchmark
+);
 criterion_main!(benches);
@@ -13,6 +13,7 @@ fn test() {
                 <body>
                     <!-- "comment" -->
                     <div hello={world} />
+                    <br>
                     <>
                         <div>"1"</div>
                         <div>"2"</div>
@@ -31,6 +32,7 @@ fn test() {
                 <body>
                     <!-- comment -->
                     <div hello="planet"></div>
+                    <br>
                     <div>1</div>
                     <div>2</div>
                     <div>3</div>
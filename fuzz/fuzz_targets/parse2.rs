@@ -0,0 +1,16 @@
+//! Feeds arbitrary input into `parse2`, which must return an error instead of
+//! panicking. Run with `cargo +nightly fuzz run parse2`.
+
+#![no_main]
+
+use std::str::FromStr;
+
+use libfuzzer_sys::fuzz_target;
+use proc_macro2::TokenStream;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else { return };
+    let Ok(tokens) = TokenStream::from_str(source) else { return };
+
+    let _ = syn_rsx::parse2(tokens);
+});
@@ -0,0 +1,83 @@
+//! Static analysis over a tree of nodes, e.g. to feed optimizations of code
+//! generation.
+
+use proc_macro2::Span;
+use syn::Expr;
+
+use crate::{Node, NodeElement, NodeName};
+
+/// Get the spans of the roots of maximal static subtrees, in document order.
+/// An element is static if it has no block name, no block attributes or
+/// spreads, no attribute values other than literals, and only static
+/// descendants without blocks. Static subtrees can be hoisted into
+/// constants.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{analyze, parse2};
+///
+/// let nodes = parse2(quote! {
+///     <div>
+///         <p class="static">"text"</p>
+///         {dynamic}
+///     </div>
+/// })
+/// .unwrap();
+///
+/// assert_eq!(analyze::static_subtrees(&nodes).len(), 1);
+/// ```
+pub fn static_subtrees(nodes: &[Node]) -> Vec<Span> {
+    let mut spans = vec![];
+    collect_static_subtrees(nodes, &mut spans);
+    spans
+}
+
+/// Whether the whole tree is static in the sense of [`static_subtrees`],
+/// i.e. it has no blocks, spreads, block tag names or attribute values other
+/// than literals anywhere. Lets code generation pick a strategy once.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{analyze, parse2};
+///
+/// assert!(analyze::is_fully_static(
+///     &parse2(quote! { <p>"text"</p> }).unwrap()
+/// ));
+/// assert!(!analyze::is_fully_static(
+///     &parse2(quote! { <p>{text}</p> }).unwrap()
+/// ));
+/// ```
+pub fn is_fully_static(nodes: &[Node]) -> bool {
+    nodes.iter().all(is_static)
+}
+
+fn collect_static_subtrees(nodes: &[Node], spans: &mut Vec<Span>) {
+    for node in nodes {
+        match node {
+            Node::Element(element) if is_static_element(element) => spans.push(element.span),
+            Node::Element(element) => collect_static_subtrees(&element.children, spans),
+            Node::Fragment(fragment) => collect_static_subtrees(&fragment.children, spans),
+            _ => (),
+        }
+    }
+}
+
+fn is_static_element(element: &NodeElement) -> bool {
+    !matches!(element.name, NodeName::Block(_))
+        && element.attributes.iter().all(is_static)
+        && element.children.iter().all(is_static)
+}
+
+fn is_static(node: &Node) -> bool {
+    match node {
+        Node::Element(element) => is_static_element(element),
+        Node::Attribute(attribute) => attribute
+            .value
+            .as_ref()
+            .iter()
+            .all(|value| matches!(value.as_ref(), Expr::Lit(_))),
+        Node::Fragment(fragment) => fragment.children.iter().all(is_static),
+        Node::Text(_) | Node::Comment(_) | Node::Doctype(_) => true,
+        Node::Block(_) => false,
+    }
+}
@@ -0,0 +1,406 @@
+//! Pluggable code generation over a tree of nodes.
+//!
+//! A [`Codegen`] gets called for every part of the tree by [`generate`] and
+//! produces a `TokenStream`, e.g. builder calls for a framework's runtime.
+
+use std::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Expr, ExprLit, Lit};
+
+use crate::{
+    ElementClassification, Node, NodeAttribute, NodeBlock, NodeComment, NodeDoctype, NodeElement,
+    NodeFragment, NodeName, NodeText,
+};
+
+/// Void elements that have neither children nor a close tag, e.g. to
+/// configure [`ParserConfig::element_classification`] with
+/// [`ElementClassification::Void`].
+///
+/// <https://developer.mozilla.org/en-US/docs/Glossary/Empty_element>
+///
+/// [`ParserConfig::element_classification`]: ../struct.ParserConfig.html#method.element_classification
+/// [`ElementClassification::Void`]: ../enum.ElementClassification.html#variant.Void
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Code generation backend, driven over the tree by [`generate`].
+///
+/// The provided methods walk the tree in source order and can be overridden
+/// to change the traversal, e.g. to skip the children of void elements.
+pub trait Codegen {
+    /// Consume the backend and return the generated code.
+    fn finish(self) -> TokenStream;
+
+    /// Called for doctypes, e.g. `<!DOCTYPE html>`.
+    fn doctype(&mut self, doctype: &NodeDoctype);
+
+    /// Called for the start of an element's open tag, before its attributes.
+    fn element_open(&mut self, element: &NodeElement);
+
+    /// Called for the end of an element's open tag, after its attributes.
+    fn element_open_end(&mut self, element: &NodeElement);
+
+    /// Called for an element's close tag, after its children.
+    fn element_close(&mut self, element: &NodeElement);
+
+    /// Called for keyed attributes, e.g. `key="value"`.
+    fn attribute(&mut self, attribute: &NodeAttribute);
+
+    /// Called for block attributes, e.g. `{props}`.
+    fn block_attribute(&mut self, block: &NodeBlock);
+
+    /// Called for text.
+    fn text(&mut self, text: &NodeText);
+
+    /// Called for comments.
+    fn comment(&mut self, comment: &NodeComment);
+
+    /// Called for block children, e.g. `{value}` in `<div>{value}</div>`.
+    fn child(&mut self, block: &NodeBlock);
+
+    /// Whether the element has neither children nor a close tag. Defaults to
+    /// elements classified as [`ElementClassification::Void`].
+    ///
+    /// [`ElementClassification::Void`]: ../enum.ElementClassification.html#variant.Void
+    fn is_void(&self, element: &NodeElement) -> bool {
+        element.classification() == ElementClassification::Void
+    }
+
+    /// Dispatch the node to the according method.
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Element(element) => self.element(element),
+            Node::Attribute(attribute) => self.attribute(attribute),
+            Node::Text(text) => self.text(text),
+            Node::Comment(comment) => self.comment(comment),
+            Node::Doctype(doctype) => self.doctype(doctype),
+            Node::Block(block) => self.child(block),
+            Node::Fragment(fragment) => self.fragment(fragment),
+        }
+    }
+
+    /// Walk the open tag, attributes, children and close tag of an element.
+    /// Void elements, see [`Codegen::is_void`], end after the open tag.
+    fn element(&mut self, element: &NodeElement) {
+        self.element_open(element);
+        for attribute in &element.attributes {
+            match attribute {
+                Node::Attribute(attribute) => self.attribute(attribute),
+                Node::Block(block) => self.block_attribute(block),
+                _ => (),
+            }
+        }
+        self.element_open_end(element);
+
+        if self.is_void(element) {
+            return;
+        }
+
+        for child in &element.children {
+            self.node(child);
+        }
+        self.element_close(element);
+    }
+
+    /// Walk the children of a fragment.
+    fn fragment(&mut self, fragment: &NodeFragment) {
+        for child in &fragment.children {
+            self.node(child);
+        }
+    }
+}
+
+/// Drive the given [`Codegen`] over the nodes and return the generated code.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{
+///     codegen::{generate, FormatStringCodegen},
+///     parse2,
+/// };
+///
+/// let nodes = parse2(quote! { <div class="x">{name}</div> }).unwrap();
+/// let tokens = generate(FormatStringCodegen::new(), &nodes);
+///
+/// assert_eq!(
+///     tokens.to_string(),
+///     quote! { format!("<div class=\"{}\">{}</div>", "x", { name }) }.to_string()
+/// );
+/// ```
+pub fn generate<C: Codegen>(mut codegen: C, nodes: &[Node]) -> TokenStream {
+    for node in nodes {
+        codegen.node(node);
+    }
+
+    codegen.finish()
+}
+
+/// Generates a `format!` call that renders the nodes to an HTML `String`.
+/// Values of text, comments, attributes and blocks, including block names,
+/// are passed as arguments and need to implement `Display`. Void elements,
+/// see [`Codegen::is_void`], have no children and close tag. Doctypes need a
+/// static value, otherwise a `compile_error!` is generated.
+#[derive(Debug, Default)]
+pub struct FormatStringCodegen {
+    format_string: String,
+    values: Vec<TokenStream>,
+    error: Option<syn::Error>,
+}
+
+impl FormatStringCodegen {
+    /// Create a new `FormatStringCodegen`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push static text, escaping the braces of the format string.
+    fn push_static(&mut self, value: &str) {
+        self.format_string
+            .push_str(&value.replace('{', "{{").replace('}', "}}"));
+    }
+
+    /// Push the name, as argument if it's a block.
+    fn push_name(&mut self, name: &NodeName) {
+        match name {
+            NodeName::Block(expr) => {
+                self.format_string.push_str("{}");
+                self.values.push(expr.to_token_stream());
+            }
+            _ => self.push_static(&name.to_string()),
+        }
+    }
+}
+
+impl Codegen for FormatStringCodegen {
+    fn finish(self) -> TokenStream {
+        if let Some(error) = self.error {
+            return error.to_compile_error();
+        }
+        let format_string = self.format_string;
+        let values = self.values;
+
+        quote! { format!(#format_string, #(#values),*) }
+    }
+
+    fn doctype(&mut self, doctype: &NodeDoctype) {
+        match String::try_from(&doctype.value) {
+            Ok(value) => self.push_static(&format!("<!DOCTYPE {}>", value)),
+            Err(_) => push_error(&mut self.error, doctype_error(doctype)),
+        }
+    }
+
+    fn element_open(&mut self, element: &NodeElement) {
+        self.format_string.push('<');
+        self.push_name(&element.name);
+    }
+
+    fn element_open_end(&mut self, _element: &NodeElement) {
+        self.format_string.push('>');
+    }
+
+    fn element_close(&mut self, element: &NodeElement) {
+        self.format_string.push_str("</");
+        self.push_name(&element.name);
+        self.format_string.push('>');
+    }
+
+    fn attribute(&mut self, attribute: &NodeAttribute) {
+        self.format_string.push(' ');
+        self.push_name(&attribute.key);
+        if let Some(value) = &attribute.value {
+            self.format_string.push_str(r#"="{}""#);
+            self.values.push(value.to_token_stream());
+        }
+    }
+
+    fn block_attribute(&mut self, block: &NodeBlock) {
+        self.format_string.push_str(" {}");
+        self.values.push(block.value.to_token_stream());
+    }
+
+    fn text(&mut self, text: &NodeText) {
+        self.format_string.push_str("{}");
+        self.values.push(text.value.to_token_stream());
+    }
+
+    fn comment(&mut self, comment: &NodeComment) {
+        self.format_string.push_str("<!-- {} -->");
+        self.values.push(comment.value.to_token_stream());
+    }
+
+    fn child(&mut self, block: &NodeBlock) {
+        self.format_string.push_str("{}");
+        self.values.push(block.value.to_token_stream());
+    }
+}
+
+/// Generates a block that renders the nodes to an HTML `String` with
+/// `push_str` calls, avoiding the nested `format!` calls of
+/// [`FormatStringCodegen`]. Static parts, including string literal values,
+/// are merged into single calls and the capacity of the `String` is
+/// reserved for them upfront. Other values, including block names, need to
+/// implement `Display`. Void elements, see [`Codegen::is_void`], have no
+/// children and close tag. Doctypes need a static value, otherwise a
+/// `compile_error!` is generated.
+#[derive(Debug, Default)]
+pub struct PushStrCodegen {
+    capacity: usize,
+    pending: String,
+    statements: Vec<TokenStream>,
+    error: Option<syn::Error>,
+}
+
+impl PushStrCodegen {
+    /// Create a new `PushStrCodegen`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push_static(&mut self, value: &str) {
+        self.capacity += value.len();
+        self.pending.push_str(value);
+    }
+
+    fn push_value(&mut self, value: &Expr) {
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => self.push_static(&lit_str.value()),
+            _ => {
+                self.flush();
+                self.statements.push(quote! {
+                    __html.push_str(&::std::string::ToString::to_string(&#value));
+                });
+            }
+        }
+    }
+
+    /// Push the name, as value if it's a block.
+    fn push_name(&mut self, name: &NodeName) {
+        match name {
+            NodeName::Block(expr) => self.push_value(expr),
+            _ => self.push_static(&name.to_string()),
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.statements.push(quote! { __html.push_str(#pending); });
+        }
+    }
+}
+
+impl Codegen for PushStrCodegen {
+    fn finish(mut self) -> TokenStream {
+        if let Some(error) = self.error {
+            return error.to_compile_error();
+        }
+        self.flush();
+        let capacity = self.capacity;
+        let statements = self.statements;
+
+        quote! {
+            {
+                let mut __html = ::std::string::String::with_capacity(#capacity);
+                #(#statements)*
+                __html
+            }
+        }
+    }
+
+    fn doctype(&mut self, doctype: &NodeDoctype) {
+        match String::try_from(&doctype.value) {
+            Ok(value) => self.push_static(&format!("<!DOCTYPE {}>", value)),
+            Err(_) => push_error(&mut self.error, doctype_error(doctype)),
+        }
+    }
+
+    fn element_open(&mut self, element: &NodeElement) {
+        self.push_static("<");
+        self.push_name(&element.name);
+    }
+
+    fn element_open_end(&mut self, _element: &NodeElement) {
+        self.push_static(">");
+    }
+
+    fn element_close(&mut self, element: &NodeElement) {
+        self.push_static("</");
+        self.push_name(&element.name);
+        self.push_static(">");
+    }
+
+    fn attribute(&mut self, attribute: &NodeAttribute) {
+        self.push_static(" ");
+        self.push_name(&attribute.key);
+        if let Some(value) = &attribute.value {
+            self.push_static("=\"");
+            self.push_value(value);
+            self.push_static("\"");
+        }
+    }
+
+    fn block_attribute(&mut self, block: &NodeBlock) {
+        self.push_static(" ");
+        self.push_value(&block.value);
+    }
+
+    fn text(&mut self, text: &NodeText) {
+        self.push_value(&text.value);
+    }
+
+    fn comment(&mut self, comment: &NodeComment) {
+        self.push_static("<!-- ");
+        self.push_value(&comment.value);
+        self.push_static(" -->");
+    }
+
+    fn child(&mut self, block: &NodeBlock) {
+        self.push_value(&block.value);
+    }
+}
+
+/// Generate a block that renders the nodes with [`PushStrCodegen`].
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{codegen::push_str_backend, parse2};
+///
+/// let nodes = parse2(quote! { <p>"Hello "{name}</p> }).unwrap();
+///
+/// assert_eq!(
+///     push_str_backend(&nodes).to_string(),
+///     quote! {
+///         {
+///             let mut __html = ::std::string::String::with_capacity(13usize);
+///             __html.push_str("<p>Hello ");
+///             __html.push_str(&::std::string::ToString::to_string(&{ name }));
+///             __html.push_str("</p>");
+///             __html
+///         }
+///     }
+///     .to_string()
+/// );
+/// ```
+pub fn push_str_backend(nodes: &[Node]) -> TokenStream {
+    generate(PushStrCodegen::new(), nodes)
+}
+
+/// Error for doctypes whose value isn't a string literal or path.
+fn doctype_error(doctype: &NodeDoctype) -> syn::Error {
+    syn::Error::new(doctype.span(), "doctype value needs to be a string or path")
+}
+
+/// Combine the error into the already collected errors.
+fn push_error(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
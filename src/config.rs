@@ -1,17 +1,134 @@
-use proc_macro2::TokenStream;
-use syn::{parse::ParseStream, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
-use crate::NodeType;
+use proc_macro2::{Span, TokenStream};
+use syn::{parse::ParseStream, Block, Result};
+
+use crate::{NodeText, NodeType};
 
 pub type TransformBlockFn = dyn Fn(ParseStream) -> Result<Option<TokenStream>>;
+pub type TransformBlockIfFn = dyn Fn(ParseStream) -> bool;
+pub type TransformTextFn = dyn Fn(&NodeText) -> Option<String>;
+pub type ValidateBlockTagNameFn = dyn Fn(&Block) -> std::result::Result<(), String>;
+
+/// Syntax of interpolations in text, see
+/// [`ParserConfig::text_interpolation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterpolationSyntax {
+    /// `${expr}`
+    DollarBrace,
+    /// `{{ expr }}`
+    DoubleBrace,
+}
+
+impl InterpolationSyntax {
+    pub(crate) fn open(&self) -> &'static str {
+        match self {
+            InterpolationSyntax::DollarBrace => "${",
+            InterpolationSyntax::DoubleBrace => "{{",
+        }
+    }
+
+    pub(crate) fn close(&self) -> &'static str {
+        match self {
+            InterpolationSyntax::DollarBrace => "}",
+            InterpolationSyntax::DoubleBrace => "}}",
+        }
+    }
+}
+
+/// How the children of an element are parsed, see
+/// [`ParserConfig::element_classification`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementClassification {
+    /// Elements without children that don't need a close tag, e.g. `<br>`.
+    Void,
+    /// Elements that can only contain text, e.g. `<script>`.
+    RawText,
+    /// Elements that can only contain text and blocks, e.g. `<textarea>`.
+    EscapableRawText,
+    /// Elements that can contain any nodes.
+    Normal,
+}
+
+/// Configures how nodes are re-emitted by [`Node::to_formatted_tokens`].
+///
+/// [`Node::to_formatted_tokens`]: enum.Node.html#method.to_formatted_tokens
+#[derive(Clone, Debug, Default)]
+pub struct FormatConfig {
+    pub(crate) self_close_empty: bool,
+    pub(crate) lowercase_names: bool,
+    pub(crate) drop_empty_attributes: bool,
+}
+
+impl FormatConfig {
+    /// Create new `FormatConfig` that re-emits nodes as they were parsed.
+    pub fn new() -> FormatConfig {
+        FormatConfig::default()
+    }
+
+    /// Emit elements without children as self-closing tags, e.g. `<div />`
+    /// for `<div></div>`.
+    pub fn self_close_empty(mut self, self_close: bool) -> Self {
+        self.self_close_empty = self_close;
+        self
+    }
+
+    /// Lowercase element names, e.g. `<div>` for `<DIV>`. Applies to all
+    /// names except blocks, including component names.
+    pub fn lowercase_names(mut self, lowercase: bool) -> Self {
+        self.lowercase_names = lowercase;
+        self
+    }
+
+    /// Drop attributes with an empty string value, e.g. `class=""`.
+    pub fn drop_empty_attributes(mut self, drop: bool) -> Self {
+        self.drop_empty_attributes = drop;
+        self
+    }
+}
 
 /// Configures the `Parser` behavior
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ParserConfig {
     pub(crate) flat_tree: bool,
+    pub(crate) collect_stats: bool,
+    pub(crate) retain_original_tokens: bool,
+    pub(crate) call_site_span: Option<Span>,
+    pub(crate) allow_unclosed_fragment: bool,
+    pub(crate) shorthand_attributes: bool,
+    pub(crate) allow_attribute_negation: bool,
+    pub(crate) detect_misplaced_attributes: bool,
+    pub(crate) require_braced_attribute_values: bool,
+    pub(crate) colon_attribute_values: bool,
+    pub(crate) comma_separated_attribute_values: bool,
+    pub(crate) interpolate_string_attributes: bool,
+    pub(crate) enforce_naming_convention: bool,
+    pub(crate) warn_redundant_void_close: bool,
+    pub(crate) text_interpolation: Option<InterpolationSyntax>,
     pub(crate) number_of_top_level_nodes: Option<usize>,
+    pub(crate) max_attributes_per_element: Option<usize>,
+    pub(crate) single_root_element: bool,
+    pub(crate) error_on_trailing_content: bool,
+    pub(crate) validate_doctype_position: bool,
+    pub(crate) chunked_recovery: bool,
     pub(crate) type_of_top_level_nodes: Option<NodeType>,
-    pub(crate) transform_block: Option<Box<TransformBlockFn>>,
+    pub(crate) transform_block: Option<Rc<TransformBlockFn>>,
+    pub(crate) transform_block_if: Option<Rc<TransformBlockIfFn>>,
+    pub(crate) respan_transformed_blocks: bool,
+    pub(crate) transform_text: Option<Rc<TransformTextFn>>,
+    pub(crate) validate_block_tag_name: Option<Rc<ValidateBlockTagNameFn>>,
+    pub(crate) template_elements: HashSet<&'static str>,
+    pub(crate) foreign_content_elements: HashSet<&'static str>,
+    pub(crate) attribute_directives: HashSet<&'static str>,
+    pub(crate) allowed_elements: Option<HashSet<&'static str>>,
+    pub(crate) allowed_attributes: Option<HashSet<&'static str>>,
+    pub(crate) element_classification: HashMap<&'static str, ElementClassification>,
+    pub(crate) case_insensitive_element_matching: bool,
+    pub(crate) forbidden_nesting: Vec<(&'static str, &'static str)>,
+    pub(crate) default_attributes: HashMap<&'static str, Vec<(&'static str, &'static str)>>,
 }
 
 impl ParserConfig {
@@ -26,18 +143,332 @@ impl ParserConfig {
         self
     }
 
+    /// Count parsed nodes, forks and tokens, e.g. to profile large templates.
+    /// The statistics are returned by [`parse2_with_stats`].
+    ///
+    /// [`parse2_with_stats`]: fn.parse2_with_stats.html
+    pub fn collect_stats(mut self, collect: bool) -> Self {
+        self.collect_stats = collect;
+        self
+    }
+
+    /// Keep a copy of the input tokens, returned by
+    /// [`Parser::original_tokens`]. Off by default since every parse clones
+    /// its input to keep it.
+    ///
+    /// [`Parser::original_tokens`]: struct.Parser.html#method.original_tokens
+    pub fn retain_original_tokens(mut self, retain: bool) -> Self {
+        self.retain_original_tokens = retain;
+        self
+    }
+
+    /// Span for errors at the end of the input, e.g. the span of the
+    /// surrounding macro call when parsing a sub-stream. By default these
+    /// errors point at the end of the given tokens.
+    pub fn call_site_span(mut self, span: Span) -> Self {
+        self.call_site_span = Some(span);
+        self
+    }
+
+    /// Close fragments that are missing their closing `</>` at the end of
+    /// the input instead of returning an error, e.g. `<><div />`. A warning
+    /// is collected for the open tag of such fragments.
+    pub fn allow_unclosed_fragment(mut self, allow: bool) -> Self {
+        self.allow_unclosed_fragment = allow;
+        self
+    }
+
+    /// Expand block attributes that only contain an identifier into keyed
+    /// attributes, e.g. `<input {value} />` into `<input value={value} />`
+    pub fn shorthand_attributes(mut self, shorthand: bool) -> Self {
+        self.shorthand_attributes = shorthand;
+        self
+    }
+
+    /// Allow a `!` prefix on attribute keys, e.g. `<input !checked />`, see
+    /// [`NodeAttribute::is_negated`].
+    ///
+    /// [`NodeAttribute::is_negated`]: struct.NodeAttribute.html#method.is_negated
+    pub fn allow_attribute_negation(mut self, allow: bool) -> Self {
+        self.allow_attribute_negation = allow;
+        self
+    }
+
+    /// Report attributes in the children of an element, e.g.
+    /// `<div> class="x"</div>`, with an error that suggests moving them into
+    /// the open tag instead of a generic error.
+    pub fn detect_misplaced_attributes(mut self, detect: bool) -> Self {
+        self.detect_misplaced_attributes = detect;
+        self
+    }
+
+    /// Require attribute values that aren't literals to be wrapped in braces
+    /// like in JSX, e.g. `key={value}` instead of `key=value`. Literals like
+    /// `key="value"` are still allowed.
+    pub fn require_braced_attribute_values(mut self, require: bool) -> Self {
+        self.require_braced_attribute_values = require;
+        self
+    }
+
+    /// Separate attribute keys and values with a single colon as an
+    /// alternative to `=`, e.g. `<div width: 100 max-width: 200 />` for
+    /// config-like DSLs.
+    ///
+    /// This is ambiguous with colon separated attribute keys like `on:click`,
+    /// which can't be used while this is enabled: a single colon after the
+    /// first identifier or dash separated name of an attribute always starts
+    /// the value. Element names and double colons like `a::b` are unaffected.
+    pub fn colon_attribute_values(mut self, colon: bool) -> Self {
+        self.colon_attribute_values = colon;
+        self
+    }
+
+    /// Parse comma separated attribute values as a list, e.g. `class=a, b`
+    /// for class lists, see [`NodeAttribute::value_list`].
+    ///
+    /// Attributes are separated by whitespace, so a comma after a value
+    /// always continues the list with the next value: `<div class=a, b c />`
+    /// has the values `a` and `b` and a separate attribute `c`. Each value is
+    /// parsed like a single value, so commas inside blocks or parentheses
+    /// don't separate values.
+    ///
+    /// [`NodeAttribute::value_list`]: struct.NodeAttribute.html#method.value_list
+    pub fn comma_separated_attribute_values(mut self, comma_separated: bool) -> Self {
+        self.comma_separated_attribute_values = comma_separated;
+        self
+    }
+
+    /// Parse `{ident}` placeholders in string literal attribute values, e.g.
+    /// `title="Hello {name}"`, see [`NodeAttribute::template`]. Like in
+    /// `format!`, `{{` and `}}` are literal braces.
+    ///
+    /// [`NodeAttribute::template`]: struct.NodeAttribute.html#method.template
+    pub fn interpolate_string_attributes(mut self, interpolate: bool) -> Self {
+        self.interpolate_string_attributes = interpolate;
+        self
+    }
+
+    /// Split string literal text at interpolations into text and block nodes,
+    /// e.g. `"Hello ${name}!"` with [`InterpolationSyntax::DollarBrace`] into
+    /// `"Hello "`, `{name}` and `"!"`. The blocks get the span of the string
+    /// literal. Braces inside string literals in interpolations aren't
+    /// supported.
+    ///
+    /// ```rust
+    /// use quote::quote;
+    /// use syn_rsx::{parse2_with_config, InterpolationSyntax, ParserConfig};
+    ///
+    /// let tokens = quote! {
+    ///     <p>"Hello ${name}!"</p>
+    /// };
+    ///
+    /// let config = ParserConfig::new().text_interpolation(Some(InterpolationSyntax::DollarBrace));
+    ///
+    /// parse2_with_config(tokens, config).unwrap();
+    /// ```
+    pub fn text_interpolation(mut self, syntax: Option<InterpolationSyntax>) -> Self {
+        self.text_interpolation = syntax;
+        self
+    }
+
+    /// Warn about element names that don't follow the convention of
+    /// PascalCase components and lowercase HTML elements, see
+    /// [`NodeName::is_component`]. Warnings are returned by
+    /// [`parse2_with_warnings`].
+    ///
+    /// [`NodeName::is_component`]: enum.NodeName.html#method.is_component
+    /// [`parse2_with_warnings`]: fn.parse2_with_warnings.html
+    pub fn enforce_naming_convention(mut self, enforce: bool) -> Self {
+        self.enforce_naming_convention = enforce;
+        self
+    }
+
+    /// Warn about close tags of void elements, e.g. `<br></br>`, see
+    /// [`ElementClassification::Void`]. The close tag is still accepted. The
+    /// warnings come with a suggestion to remove the close tag and are
+    /// returned by [`parse2_with_warnings`].
+    ///
+    /// [`ElementClassification::Void`]: enum.ElementClassification.html#variant.Void
+    /// [`parse2_with_warnings`]: fn.parse2_with_warnings.html
+    pub fn warn_redundant_void_close(mut self, warn: bool) -> Self {
+        self.warn_redundant_void_close = warn;
+        self
+    }
+
     /// Exact number of required top level nodes
     pub fn number_of_top_level_nodes(mut self, number: usize) -> Self {
         self.number_of_top_level_nodes = Some(number);
         self
     }
 
+    /// Maximum number of attributes per element, including block attributes.
+    /// Guards tooling against runaway generated markup.
+    pub fn max_attributes_per_element(mut self, max: usize) -> Self {
+        self.max_attributes_per_element = Some(max);
+        self
+    }
+
+    /// Report the first top level node after the expected number of top
+    /// level nodes at its span, e.g. accidental markup after the root
+    /// element. Expects [`number_of_top_level_nodes`] if set, else one node.
+    /// Doctypes and comments aren't counted and are allowed anywhere.
+    ///
+    /// [`number_of_top_level_nodes`]: struct.ParserConfig.html#method.number_of_top_level_nodes
+    pub fn error_on_trailing_content(mut self, error: bool) -> Self {
+        self.error_on_trailing_content = error;
+        self
+    }
+
+    /// Require top level doctypes to come before any content other than
+    /// comments, e.g. to catch a doctype pasted after the `<html>` element.
+    pub fn validate_doctype_position(mut self, validate: bool) -> Self {
+        self.validate_doctype_position = validate;
+        self
+    }
+
+    /// Split the source into chunks at line boundaries in [`parse_str_chunked`]
+    /// and parse each chunk on its own, so an error like an unclosed brace
+    /// doesn't prevent parsing the rest of the source. Without it the whole
+    /// source is a single chunk.
+    ///
+    /// [`parse_str_chunked`]: fn.parse_str_chunked.html
+    pub fn chunked_recovery(mut self, chunked: bool) -> Self {
+        self.chunked_recovery = chunked;
+        self
+    }
+
+    /// Require exactly one top level element or fragment. Unlike
+    /// [`number_of_top_level_nodes`], top level comments and doctypes are
+    /// allowed next to it.
+    ///
+    /// [`number_of_top_level_nodes`]: struct.ParserConfig.html#method.number_of_top_level_nodes
+    pub fn single_root_element(mut self, single: bool) -> Self {
+        self.single_root_element = single;
+        self
+    }
+
     /// Enforce the `NodeType` of top level nodes
     pub fn type_of_top_level_nodes(mut self, node_type: NodeType) -> Self {
         self.type_of_top_level_nodes = Some(node_type);
         self
     }
 
+    /// Element names whose children are inert template content, e.g.
+    /// `template`. Children are parsed as usual, but the element is flagged,
+    /// see [`NodeElement::is_template`].
+    ///
+    /// [`NodeElement::is_template`]: struct.NodeElement.html#method.is_template
+    pub fn template_elements(mut self, elements: HashSet<&'static str>) -> Self {
+        self.template_elements = elements;
+        self
+    }
+
+    /// Element names that start foreign content, e.g. `svg` and `math`. The
+    /// elements and their descendants are flagged, see
+    /// [`NodeElement::is_foreign_content`].
+    ///
+    /// [`NodeElement::is_foreign_content`]: struct.NodeElement.html#method.is_foreign_content
+    pub fn foreign_content_elements(mut self, elements: HashSet<&'static str>) -> Self {
+        self.foreign_content_elements = elements;
+        self
+    }
+
+    /// Warn about elements nested inside an element they must not be
+    /// contained in. Each pair is `(ancestor, descendant)`, e.g. `("a", "a")`
+    /// for links inside links or `("span", "div")` for block elements inside
+    /// inline elements. Warnings are returned by [`parse2_with_warnings`].
+    ///
+    /// [`parse2_with_warnings`]: fn.parse2_with_warnings.html
+    pub fn forbidden_nesting(mut self, pairs: Vec<(&'static str, &'static str)>) -> Self {
+        self.forbidden_nesting = pairs;
+        self
+    }
+
+    /// Attributes to add to elements with the given names, e.g.
+    /// `("button", vec![("type", "button")])`, unless the element already has
+    /// an attribute with the same key. The attributes are string literals
+    /// with call-site spans. They're checked against
+    /// [`allowed_attributes`] and [`max_attributes_per_element`] like written
+    /// attributes.
+    ///
+    /// [`allowed_attributes`]: struct.ParserConfig.html#method.allowed_attributes
+    /// [`max_attributes_per_element`]: struct.ParserConfig.html#method.max_attributes_per_element
+    pub fn default_attributes(
+        mut self,
+        attributes: HashMap<&'static str, Vec<(&'static str, &'static str)>>,
+    ) -> Self {
+        self.default_attributes = attributes;
+        self
+    }
+
+    /// Classify elements by name to parse their children accordingly.
+    /// Elements that aren't classified are [`ElementClassification::Normal`].
+    ///
+    /// Void elements can omit the `/` of self-closing tags, e.g. `<br>`.
+    /// Since the parser works on tokens, raw text elements can't contain
+    /// unquoted text, instead their children are restricted to text nodes.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use quote::quote;
+    /// use syn_rsx::{parse2_with_config, ElementClassification, ParserConfig};
+    ///
+    /// let tokens = quote! {
+    ///     <div><br><script>"let x = 1;"</script></div>
+    /// };
+    ///
+    /// let config = ParserConfig::new().element_classification(HashMap::from([
+    ///     ("br", ElementClassification::Void),
+    ///     ("script", ElementClassification::RawText),
+    /// ]));
+    ///
+    /// parse2_with_config(tokens, config).unwrap();
+    /// ```
+    pub fn element_classification(
+        mut self,
+        classification: HashMap<&'static str, ElementClassification>,
+    ) -> Self {
+        self.element_classification = classification;
+        self
+    }
+
+    /// Match element names case-insensitively against
+    /// [`element_classification`], which needs to have lowercase names, e.g.
+    /// to treat `<SCRIPT>` like `<script>` as in HTML. Names are matched
+    /// exactly by default.
+    ///
+    /// [`element_classification`]: struct.ParserConfig.html#method.element_classification
+    pub fn case_insensitive_element_matching(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive_element_matching = case_insensitive;
+        self
+    }
+
+    /// Only allow elements with the given names, e.g. to sanitize
+    /// user-provided templates. Other elements, including elements with block
+    /// names, result in an error. `None` allows all elements.
+    pub fn allowed_elements(mut self, elements: Option<HashSet<&'static str>>) -> Self {
+        self.allowed_elements = elements;
+        self
+    }
+
+    /// Only allow attributes with the given keys. Other attributes, including
+    /// attributes with block keys, result in an error. Block attributes like
+    /// `{props}` are not affected. `None` allows all attributes.
+    pub fn allowed_attributes(mut self, attributes: Option<HashSet<&'static str>>) -> Self {
+        self.allowed_attributes = attributes;
+        self
+    }
+
+    /// Attribute prefixes that mark directives, e.g. `class` for
+    /// `class:active={flag}`, see [`NodeAttribute::directive`].
+    ///
+    /// [`NodeAttribute::directive`]: struct.NodeAttribute.html#method.directive
+    pub fn attribute_directives(mut self, directives: HashSet<&'static str>) -> Self {
+        self.attribute_directives = directives;
+        self
+    }
+
     /// Transforms the `value` of all `NodeType::Block`s with the given closure
     /// callback. The provided `ParseStream` is the content of the block.
     ///
@@ -73,7 +504,296 @@ impl ParserConfig {
     where
         F: Fn(ParseStream) -> Result<Option<TokenStream>> + 'static,
     {
-        self.transform_block = Some(Box::new(callback));
+        self.transform_block = Some(Rc::new(callback));
         self
     }
+
+    /// Only call the [`transform_block`] callback for blocks where the given
+    /// predicate returns `true`. The predicate receives the block content
+    /// and should only peek at it, which is cheaper than forking the block
+    /// into the transform callback just to return `None`.
+    ///
+    /// ```rust
+    /// use quote::quote;
+    /// use syn::Token;
+    /// use syn_rsx::{parse2_with_config, ParserConfig};
+    ///
+    /// let tokens = quote! {
+    ///     <div>{%}{"not transformed"}</div>
+    /// };
+    ///
+    /// let config = ParserConfig::new()
+    ///     .transform_block(|input| {
+    ///         input.parse::<Token![%]>()?;
+    ///         Ok(Some(quote! { "percent" }))
+    ///     })
+    ///     .transform_block_if(|input| input.peek(Token![%]));
+    ///
+    /// parse2_with_config(tokens, config).unwrap();
+    /// ```
+    ///
+    /// [`transform_block`]: struct.ParserConfig.html#method.transform_block
+    pub fn transform_block_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(ParseStream) -> bool + 'static,
+    {
+        self.transform_block_if = Some(Rc::new(predicate));
+        self
+    }
+
+    /// Give the tokens returned by the [`transform_block`] callback the span
+    /// of the original block, so errors in them point at the block in the
+    /// user's source instead of the code that created the tokens.
+    ///
+    /// [`transform_block`]: struct.ParserConfig.html#method.transform_block
+    pub fn respan_transformed_blocks(mut self, respan: bool) -> Self {
+        self.respan_transformed_blocks = respan;
+        self
+    }
+
+    /// Transforms the `value` of all `NodeType::Text`s with the given closure
+    /// callback, e.g. to normalize or escape text while parsing.
+    ///
+    /// When `Some(String)` is returned, the text value is replaced by a string
+    /// literal with the returned content and the span of the original text.
+    /// If `None` is returned, the original text is kept.
+    ///
+    /// ```rust
+    /// use std::convert::TryFrom;
+    ///
+    /// use quote::quote;
+    /// use syn_rsx::{parse2_with_config, ParserConfig};
+    ///
+    /// let tokens = quote! {
+    ///     <div>"hello"</div>
+    /// };
+    ///
+    /// let config = ParserConfig::new().transform_text(|text| {
+    ///     String::try_from(&text.value)
+    ///         .ok()
+    ///         .map(|text| text.to_uppercase())
+    /// });
+    ///
+    /// parse2_with_config(tokens, config).unwrap();
+    /// ```
+    pub fn transform_text<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&NodeText) -> Option<String> + 'static,
+    {
+        self.transform_text = Some(Rc::new(callback));
+        self
+    }
+
+    /// Validate block tag names like `<{Component}>` with the given callback,
+    /// e.g. to only allow paths. An `Err` is reported at the block with the
+    /// returned message.
+    ///
+    /// ```rust
+    /// use quote::quote;
+    /// use syn::{Expr, Stmt};
+    /// use syn_rsx::{parse2_with_config, ParserConfig};
+    ///
+    /// let config =
+    ///     ParserConfig::new().validate_block_tag_name(|block| match block.stmts.as_slice() {
+    ///         [Stmt::Expr(Expr::Path(_))] => Ok(()),
+    ///         _ => Err("block tag names must be paths".to_owned()),
+    ///     });
+    ///
+    /// let error = parse2_with_config(quote! { <{make()} /> }, config).unwrap_err();
+    /// assert_eq!(error.to_string(), "block tag names must be paths");
+    /// ```
+    pub fn validate_block_tag_name<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Block) -> std::result::Result<(), String> + 'static,
+    {
+        self.validate_block_tag_name = Some(Rc::new(callback));
+        self
+    }
+}
+
+/// Getters to read a config back, e.g. to pass the same element
+/// classification to a serializer. They're prefixed with `get_` since the
+/// builder methods have the plain names.
+impl ParserConfig {
+    /// Whether a flat tree is returned, see [`ParserConfig::flat_tree`].
+    pub fn get_flat_tree(&self) -> bool {
+        self.flat_tree
+    }
+
+    /// See [`ParserConfig::collect_stats`].
+    pub fn get_collect_stats(&self) -> bool {
+        self.collect_stats
+    }
+
+    /// See [`ParserConfig::retain_original_tokens`].
+    pub fn get_retain_original_tokens(&self) -> bool {
+        self.retain_original_tokens
+    }
+
+    /// See [`ParserConfig::call_site_span`].
+    pub fn get_call_site_span(&self) -> Option<Span> {
+        self.call_site_span
+    }
+
+    /// See [`ParserConfig::allow_unclosed_fragment`].
+    pub fn get_allow_unclosed_fragment(&self) -> bool {
+        self.allow_unclosed_fragment
+    }
+
+    /// See [`ParserConfig::shorthand_attributes`].
+    pub fn get_shorthand_attributes(&self) -> bool {
+        self.shorthand_attributes
+    }
+
+    /// See [`ParserConfig::allow_attribute_negation`].
+    pub fn get_allow_attribute_negation(&self) -> bool {
+        self.allow_attribute_negation
+    }
+
+    /// See [`ParserConfig::detect_misplaced_attributes`].
+    pub fn get_detect_misplaced_attributes(&self) -> bool {
+        self.detect_misplaced_attributes
+    }
+
+    /// See [`ParserConfig::require_braced_attribute_values`].
+    pub fn get_require_braced_attribute_values(&self) -> bool {
+        self.require_braced_attribute_values
+    }
+
+    /// See [`ParserConfig::colon_attribute_values`].
+    pub fn get_colon_attribute_values(&self) -> bool {
+        self.colon_attribute_values
+    }
+
+    /// See [`ParserConfig::comma_separated_attribute_values`].
+    pub fn get_comma_separated_attribute_values(&self) -> bool {
+        self.comma_separated_attribute_values
+    }
+
+    /// See [`ParserConfig::interpolate_string_attributes`].
+    pub fn get_interpolate_string_attributes(&self) -> bool {
+        self.interpolate_string_attributes
+    }
+
+    /// See [`ParserConfig::enforce_naming_convention`].
+    pub fn get_enforce_naming_convention(&self) -> bool {
+        self.enforce_naming_convention
+    }
+
+    /// See [`ParserConfig::warn_redundant_void_close`].
+    pub fn get_warn_redundant_void_close(&self) -> bool {
+        self.warn_redundant_void_close
+    }
+
+    /// See [`ParserConfig::text_interpolation`].
+    pub fn get_text_interpolation(&self) -> Option<InterpolationSyntax> {
+        self.text_interpolation
+    }
+
+    /// See [`ParserConfig::number_of_top_level_nodes`].
+    pub fn get_number_of_top_level_nodes(&self) -> Option<usize> {
+        self.number_of_top_level_nodes
+    }
+
+    /// See [`ParserConfig::max_attributes_per_element`].
+    pub fn get_max_attributes_per_element(&self) -> Option<usize> {
+        self.max_attributes_per_element
+    }
+
+    /// See [`ParserConfig::single_root_element`].
+    pub fn get_single_root_element(&self) -> bool {
+        self.single_root_element
+    }
+
+    /// See [`ParserConfig::error_on_trailing_content`].
+    pub fn get_error_on_trailing_content(&self) -> bool {
+        self.error_on_trailing_content
+    }
+
+    /// See [`ParserConfig::validate_doctype_position`].
+    pub fn get_validate_doctype_position(&self) -> bool {
+        self.validate_doctype_position
+    }
+
+    /// See [`ParserConfig::chunked_recovery`].
+    pub fn get_chunked_recovery(&self) -> bool {
+        self.chunked_recovery
+    }
+
+    /// See [`ParserConfig::type_of_top_level_nodes`].
+    pub fn get_type_of_top_level_nodes(&self) -> Option<&NodeType> {
+        self.type_of_top_level_nodes.as_ref()
+    }
+
+    /// See [`ParserConfig::transform_block`].
+    pub fn get_transform_block(&self) -> Option<&TransformBlockFn> {
+        self.transform_block.as_deref()
+    }
+
+    /// See [`ParserConfig::transform_block_if`].
+    pub fn get_transform_block_if(&self) -> Option<&TransformBlockIfFn> {
+        self.transform_block_if.as_deref()
+    }
+
+    /// See [`ParserConfig::respan_transformed_blocks`].
+    pub fn get_respan_transformed_blocks(&self) -> bool {
+        self.respan_transformed_blocks
+    }
+
+    /// See [`ParserConfig::transform_text`].
+    pub fn get_transform_text(&self) -> Option<&TransformTextFn> {
+        self.transform_text.as_deref()
+    }
+
+    /// See [`ParserConfig::validate_block_tag_name`].
+    pub fn get_validate_block_tag_name(&self) -> Option<&ValidateBlockTagNameFn> {
+        self.validate_block_tag_name.as_deref()
+    }
+
+    /// See [`ParserConfig::template_elements`].
+    pub fn get_template_elements(&self) -> &HashSet<&'static str> {
+        &self.template_elements
+    }
+
+    /// See [`ParserConfig::foreign_content_elements`].
+    pub fn get_foreign_content_elements(&self) -> &HashSet<&'static str> {
+        &self.foreign_content_elements
+    }
+
+    /// See [`ParserConfig::attribute_directives`].
+    pub fn get_attribute_directives(&self) -> &HashSet<&'static str> {
+        &self.attribute_directives
+    }
+
+    /// See [`ParserConfig::allowed_elements`].
+    pub fn get_allowed_elements(&self) -> Option<&HashSet<&'static str>> {
+        self.allowed_elements.as_ref()
+    }
+
+    /// See [`ParserConfig::allowed_attributes`].
+    pub fn get_allowed_attributes(&self) -> Option<&HashSet<&'static str>> {
+        self.allowed_attributes.as_ref()
+    }
+
+    /// See [`ParserConfig::element_classification`].
+    pub fn get_element_classification(&self) -> &HashMap<&'static str, ElementClassification> {
+        &self.element_classification
+    }
+
+    /// See [`ParserConfig::case_insensitive_element_matching`].
+    pub fn get_case_insensitive_element_matching(&self) -> bool {
+        self.case_insensitive_element_matching
+    }
+
+    /// See [`ParserConfig::forbidden_nesting`].
+    pub fn get_forbidden_nesting(&self) -> &[(&'static str, &'static str)] {
+        &self.forbidden_nesting
+    }
+
+    /// See [`ParserConfig::default_attributes`].
+    pub fn get_default_attributes(
+        &self,
+    ) -> &HashMap<&'static str, Vec<(&'static str, &'static str)>> {
+        &self.default_attributes
+    }
 }
@@ -0,0 +1,118 @@
+//! Structured diagnostics for consumers outside of proc macros, e.g. CLI
+//! linters that print errors themselves.
+//!
+//! Everything but [`Warning`] needs the `span-locations` feature, which
+//! enables line and column information of `proc-macro2` spans.
+
+#[cfg(feature = "span-locations")]
+use proc_macro2::LineColumn;
+use proc_macro2::Span;
+#[cfg(feature = "span-locations")]
+use syn::Error;
+
+/// Severity of a [`SimpleDiagnostic`].
+#[cfg(feature = "span-locations")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// Plain diagnostic with message, level and source location.
+///
+/// Lines are 1-indexed and columns are 0-indexed, in UTF-8 characters.
+#[cfg(feature = "span-locations")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimpleDiagnostic {
+    /// The diagnostic message.
+    pub message: String,
+    /// The severity of the diagnostic.
+    pub level: Level,
+    /// Start of the span the diagnostic points at.
+    pub start: LineColumn,
+    /// End of the span the diagnostic points at.
+    pub end: LineColumn,
+    /// Fix that can be applied automatically, e.g. by an editor.
+    pub suggestion: Option<Suggestion>,
+}
+
+/// Replacement of the source between `start` and `end` that fixes a
+/// [`SimpleDiagnostic`]. An empty range inserts the replacement.
+#[cfg(feature = "span-locations")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Start of the span to replace.
+    pub start: LineColumn,
+    /// End of the span to replace.
+    pub end: LineColumn,
+    /// The text to replace the span with.
+    pub replacement: String,
+}
+
+#[cfg(feature = "span-locations")]
+impl SimpleDiagnostic {
+    /// Convert a [`syn::Error`] into error diagnostics, one for every message
+    /// combined into the error.
+    pub fn from_error(error: &Error) -> Vec<SimpleDiagnostic> {
+        error
+            .into_iter()
+            .map(|error| SimpleDiagnostic::from(&error))
+            .collect()
+    }
+}
+
+#[cfg(feature = "span-locations")]
+impl From<&Error> for SimpleDiagnostic {
+    /// Convert the first message of the given error. See
+    /// [`SimpleDiagnostic::from_error`] to convert all combined messages.
+    fn from(error: &Error) -> Self {
+        let span = error.span();
+
+        Self {
+            message: error.to_string(),
+            level: Level::Error,
+            start: span.start(),
+            end: span.end(),
+            suggestion: None,
+        }
+    }
+}
+
+/// Split the diagnostics into errors and warnings, e.g. to emit errors as
+/// `compile_error!` and warnings with a softer mechanism.
+#[cfg(feature = "span-locations")]
+pub fn partition(
+    diagnostics: Vec<SimpleDiagnostic>,
+) -> (Vec<SimpleDiagnostic>, Vec<SimpleDiagnostic>) {
+    diagnostics
+        .into_iter()
+        .partition(|diagnostic| diagnostic.level == Level::Error)
+}
+
+/// Warning collected while parsing, see [`parse2_with_warnings`]. Unlike
+/// errors, warnings don't stop parsing.
+///
+/// [`parse2_with_warnings`]: ../fn.parse2_with_warnings.html
+#[derive(Clone, Debug)]
+pub struct Warning {
+    /// The warning message.
+    pub message: String,
+    /// Span the warning points at.
+    pub span: Span,
+    /// Fix that can be applied automatically, e.g. by an editor.
+    #[cfg(feature = "span-locations")]
+    pub suggestion: Option<Suggestion>,
+}
+
+#[cfg(feature = "span-locations")]
+impl From<&Warning> for SimpleDiagnostic {
+    fn from(warning: &Warning) -> Self {
+        Self {
+            message: warning.message.clone(),
+            level: Level::Warning,
+            start: warning.span.start(),
+            end: warning.span.end(),
+            suggestion: warning.suggestion.clone(),
+        }
+    }
+}
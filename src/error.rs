@@ -2,4 +2,11 @@
 pub enum Error {
     #[error("TryFrom failed: {0}")]
     TryFrom(String),
+
+    #[error(transparent)]
+    Parse(#[from] syn::Error),
+
+    /// One combined error with an entry per dynamic part.
+    #[error("{0}")]
+    Dynamic(syn::Error),
 }
@@ -0,0 +1,401 @@
+//! HTML output helpers.
+
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    fmt::{self, Write as _},
+    io::{self, Write},
+    ops::Range,
+};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{spanned::Spanned, Expr, ExprLit, Lit};
+
+use crate::{ElementClassification, Node, NodeElement};
+
+/// Names of the HTML elements.
+pub(crate) const ELEMENT_NAMES: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// Offsets in generated output mapped to the spans of the nodes they were
+/// generated from, see [`to_html_with_source_map`].
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    entries: Vec<(Range<usize>, Span)>,
+}
+
+impl SourceMap {
+    /// Byte ranges of the output of each node, paired with the node's span,
+    /// in the order the output begins.
+    pub fn entries(&self) -> &[(Range<usize>, Span)] {
+        &self.entries
+    }
+
+    /// Span of the innermost node whose output contains the given byte
+    /// offset, e.g. the element for an offset in its close tag.
+    pub fn span_at(&self, offset: usize) -> Option<Span> {
+        // Nested nodes come after their parents, so of ranges with the same
+        // length the last one is the innermost node.
+        self.entries
+            .iter()
+            .rev()
+            .filter(|(range, _)| range.contains(&offset))
+            .min_by_key(|(range, _)| range.len())
+            .map(|(_, span)| *span)
+    }
+}
+
+/// Write the nodes to the given writer like their `Display` implementations,
+/// but with escaped text and attribute values and without close tags for
+/// elements classified as [`ElementClassification::Void`]. Consecutive dashes
+/// in comments are separated by a space, so that comments can't be closed
+/// early.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{html::write_html, parse2};
+///
+/// let nodes = parse2(quote! { <div class="x">"a < b"</div> }).unwrap();
+/// let mut html = vec![];
+/// write_html(&mut html, &nodes).unwrap();
+///
+/// assert_eq!(html, br#"<div class="x">a &lt; b</div>"#);
+/// ```
+///
+/// [`ElementClassification::Void`]: ../enum.ElementClassification.html#variant.Void
+// `io::Error::other` needs Rust 1.74.
+#[allow(clippy::io_other_error)]
+pub fn write_html<W: Write>(w: &mut W, nodes: &[Node]) -> io::Result<()> {
+    let mut out = IoWriter { w, error: None };
+    let mut writer = HtmlWriter::new(&mut out, &is_classified_void);
+
+    writer.nodes(nodes).map_err(|_| {
+        out.error
+            .take()
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "formatter error"))
+    })
+}
+
+/// Adapter to write formatted output into an [`io::Write`], keeping the
+/// underlying error.
+struct IoWriter<W> {
+    w: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.w.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Serialize the nodes like [`write_html`] and record the range of the
+/// output of every node, including attributes.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{html::to_html_with_source_map, parse2};
+///
+/// let nodes = parse2(quote! { <div class="x">"hello"</div> }).unwrap();
+/// let (html, source_map) = to_html_with_source_map(&nodes);
+///
+/// assert_eq!(html, r#"<div class="x">hello</div>"#);
+/// assert_eq!(source_map.entries().len(), 3);
+/// assert_eq!(source_map.entries()[0].0, 0..html.len());
+/// ```
+pub fn to_html_with_source_map(nodes: &[Node]) -> (String, SourceMap) {
+    let mut html = String::new();
+    let mut source_map = SourceMap::default();
+    let mut writer = HtmlWriter::new(&mut html, &is_classified_void);
+    writer.source_map = Some(&mut source_map);
+    // Writing into a `String` can't fail.
+    let _ = writer.nodes(nodes);
+
+    (html, source_map)
+}
+
+fn is_classified_void(element: &NodeElement) -> bool {
+    element.classification() == ElementClassification::Void
+}
+
+/// Serialize the nodes like their `Display` implementations, but with
+/// escaped text and attribute values. The elements in `void_set` are written
+/// without children and close tag.
+pub(crate) fn write_html_with_void_set(
+    html: &mut String,
+    nodes: &[Node],
+    void_set: &HashSet<&str>,
+) {
+    let is_void = |element: &NodeElement| void_set.contains(element.name.to_string().as_str());
+    let mut writer = HtmlWriter::new(html, &is_void);
+    // Writing into a `String` can't fail.
+    let _ = writer.nodes(nodes);
+}
+
+/// Writes nodes as HTML, escaping text and attribute values.
+struct HtmlWriter<'a, W> {
+    out: W,
+    is_void: &'a dyn Fn(&NodeElement) -> bool,
+    /// Records the output range of every node if set.
+    source_map: Option<&'a mut SourceMap>,
+    /// Number of bytes written so far.
+    len: usize,
+}
+
+impl<'a, W: fmt::Write> HtmlWriter<'a, W> {
+    fn new(out: W, is_void: &'a dyn Fn(&NodeElement) -> bool) -> Self {
+        Self {
+            out,
+            is_void,
+            source_map: None,
+            len: 0,
+        }
+    }
+
+    fn nodes(&mut self, nodes: &[Node]) -> fmt::Result {
+        for node in nodes {
+            self.node(node)?;
+        }
+
+        Ok(())
+    }
+
+    fn node(&mut self, node: &Node) -> fmt::Result {
+        let start = self.len;
+        let entry = self.source_map.as_mut().map(|source_map| {
+            source_map.entries.push((start..start, node.span()));
+            source_map.entries.len() - 1
+        });
+
+        self.node_content(node)?;
+
+        if let (Some(source_map), Some(entry)) = (self.source_map.as_mut(), entry) {
+            source_map.entries[entry].0.end = self.len;
+        }
+
+        Ok(())
+    }
+
+    fn node_content(&mut self, node: &Node) -> fmt::Result {
+        match node {
+            Node::Element(element) => {
+                write!(self, "<{}", element.name)?;
+                for attribute in &element.attributes {
+                    self.write_char(' ')?;
+                    self.node(attribute)?;
+                }
+                self.write_char('>')?;
+                if !(self.is_void)(element) {
+                    self.nodes(&element.children)?;
+                    write!(self, "</{}>", element.name)?;
+                }
+                Ok(())
+            }
+            Node::Attribute(attribute) => {
+                write!(self, "{}", attribute.key)?;
+                match &attribute.value {
+                    Some(value) => match String::try_from(value)
+                        .or_else(|_| literal_value(value.as_ref()).ok_or(()))
+                    {
+                        Ok(value) => {
+                            self.write_str("=\"")?;
+                            self.escaped(&value)?;
+                            self.write_char('"')
+                        }
+                        Err(_) if matches!(value.as_ref(), Expr::Block(_)) => {
+                            self.write_char('=')?;
+                            self.escaped(&value.to_token_stream().to_string())
+                        }
+                        Err(_) => {
+                            self.write_str("={")?;
+                            self.escaped(&value.to_token_stream().to_string())?;
+                            self.write_char('}')
+                        }
+                    },
+                    None => Ok(()),
+                }
+            }
+            Node::Text(text) => match String::try_from(&text.value) {
+                Ok(value) => self.escaped(&value),
+                Err(_) => self.escaped(&text.value.to_token_stream().to_string()),
+            },
+            Node::Block(block) => self.escaped(&block.to_string()),
+            Node::Fragment(fragment) => self.nodes(&fragment.children),
+            Node::Comment(comment) => {
+                self.write_str("<!-- ")?;
+                self.comment_text(&comment.text())?;
+                self.write_str(" -->")
+            }
+            Node::Doctype(_) => write!(self, "{}", node),
+        }
+    }
+
+    /// Write the text of a comment with a space between consecutive dashes,
+    /// so that it can't close the comment early, e.g. `a- ->b` for `a-->b`.
+    fn comment_text(&mut self, text: &str) -> fmt::Result {
+        let mut dash = false;
+        for char in text.chars() {
+            if dash && char == '-' {
+                self.write_char(' ')?;
+            }
+            dash = char == '-';
+            self.write_char(char)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the text with `&`, `<`, `>` and `"` escaped.
+    fn escaped(&mut self, text: &str) -> fmt::Result {
+        for char in text.chars() {
+            match char {
+                '&' => self.write_str("&amp;")?,
+                '<' => self.write_str("&lt;")?,
+                '>' => self.write_str("&gt;")?,
+                '"' => self.write_str("&quot;")?,
+                _ => self.write_char(char)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for HtmlWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.len += s.len();
+        self.out.write_str(s)
+    }
+}
+
+/// Get the value of a literal other than a string, e.g. `1` of `1u8` or `c`
+/// of `'c'`, to write it as attribute value.
+fn literal_value(expr: &Expr) -> Option<String> {
+    let Expr::Lit(ExprLit { lit, .. }) = expr else {
+        return None;
+    };
+
+    match lit {
+        Lit::Int(int) => Some(int.base10_digits().to_owned()),
+        Lit::Float(float) => Some(float.base10_digits().to_owned()),
+        Lit::Bool(bool) => Some(bool.value.to_string()),
+        Lit::Char(char) => Some(char.value().to_string()),
+        _ => None,
+    }
+}
@@ -141,14 +141,14 @@
 //! - **Customization**
 //!
 //!   A [`ParserConfig`] to customize parsing behavior is available, so if you
-//! have   slightly different requirements for parsing and it's not yet
-//! customizable   feel free to open an issue or pull request to extend the
-//! configuration.
+//!   have slightly different requirements for parsing and it's not yet
+//!   customizable feel free to open an issue or pull request to extend the
+//!   configuration.
 //!
 //!   One highlight with regards to customization is the [`transform_block`]
 //!   configuration, which takes a closure that receives raw block content as
 //!   `ParseStream` and lets you optionally convert it to a `TokenStream`. That
-//! makes it   possible to have custom syntax in blocks. More details in [#9]
+//!   makes it possible to have custom syntax in blocks. More details in [#9]
 //!
 //!
 //! [`syn`]: /syn
@@ -163,15 +163,28 @@
 
 extern crate proc_macro;
 
+use std::collections::HashSet;
+
+#[cfg(feature = "span-locations")]
+use diagnostic::SimpleDiagnostic;
+use diagnostic::Warning;
 use syn::{
     parse::{ParseStream, Parser as _},
     Result,
 };
 
+pub mod analyze;
+pub mod codegen;
 mod config;
+pub mod diagnostic;
 mod error;
+pub mod html;
 mod node;
 mod parser;
+pub mod select;
+pub mod slots;
+mod tree;
+pub mod xml;
 
 pub mod punctuation {
     //! Custom syn punctuations
@@ -180,10 +193,11 @@ pub mod punctuation {
     custom_punctuation!(Dash, -);
 }
 
-pub use config::ParserConfig;
+pub use config::{ElementClassification, FormatConfig, InterpolationSyntax, ParserConfig};
 pub use error::Error;
 pub use node::*;
-pub use parser::Parser;
+pub use parser::{Event, ParseStats, Parser};
+pub use tree::*;
 
 /// Parse the given [`proc-macro::TokenStream`] into a [`Node`] tree.
 ///
@@ -234,3 +248,391 @@ pub fn parse2_with_config(
 
     parser.parse2(tokens)
 }
+
+/// Parse the rest of the given [`ParseStream`] into a [`Node`] tree, to
+/// embed RSX in larger syntax like the body of a function. Inside a brace
+/// group the stream ends at its closing brace, so parsing stops there.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn::{
+///     braced,
+///     parse::{Parse, ParseStream},
+///     Signature, Stmt, Token,
+/// };
+/// use syn_rsx::{parse_in_expr_position, Node};
+///
+/// struct Component {
+///     signature: Signature,
+///     stmts: Vec<Stmt>,
+///     nodes: Vec<Node>,
+/// }
+///
+/// impl Parse for Component {
+///     fn parse(input: ParseStream) -> syn::Result<Self> {
+///         let signature = input.parse()?;
+///         let content;
+///         braced!(content in input);
+///         let mut stmts = vec![];
+///         while !content.peek(Token![<]) {
+///             stmts.push(content.parse()?);
+///         }
+///         let nodes = parse_in_expr_position(&content)?;
+///
+///         Ok(Component { signature, stmts, nodes })
+///     }
+/// }
+///
+/// let component: Component = syn::parse2(quote! {
+///     fn f() -> impl View { let x = 1; <div>{x}</div> }
+/// })
+/// .unwrap();
+///
+/// assert_eq!(component.signature.ident, "f");
+/// assert_eq!(component.stmts.len(), 1);
+/// assert_eq!(component.nodes.len(), 1);
+/// ```
+///
+/// [`ParseStream`]: https://docs.rs/syn/1/syn/parse/type.ParseStream.html
+/// [`Node`]: struct.Node.html
+pub fn parse_in_expr_position(input: ParseStream) -> Result<Vec<Node>> {
+    Parser::new(ParserConfig::default()).parse(input)
+}
+
+/// Parse the given [`proc-macro2::TokenStream`] with custom
+/// [`ParserConfig`] and pass the [`Node`] tree to the given code generation,
+/// or emit the parse error as `compile_error!` tokens. Covers the usual
+/// body of a proc macro.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{
+///     codegen::{generate, FormatStringCodegen},
+///     parse2_to_tokens, ParserConfig,
+/// };
+///
+/// let tokens = parse2_to_tokens(quote! { <div> }, ParserConfig::new(), |nodes| {
+///     generate(FormatStringCodegen::new(), &nodes)
+/// });
+///
+/// assert!(tokens.to_string().starts_with("compile_error !"));
+/// ```
+///
+/// [`proc-macro2::TokenStream`]: https://docs.rs/proc-macro2/latest/proc_macro2/struct.TokenStream.html
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+pub fn parse2_to_tokens<F>(
+    tokens: proc_macro2::TokenStream,
+    config: ParserConfig,
+    codegen: F,
+) -> proc_macro2::TokenStream
+where
+    F: FnOnce(Vec<Node>) -> proc_macro2::TokenStream,
+{
+    match parse2_with_config(tokens, config) {
+        Ok(nodes) => codegen(nodes),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Parse the given [`proc-macro2::TokenStream`] and serialize it to HTML
+/// right away, writing the elements in `void_set` without children and close
+/// tag. Text and attribute values are escaped. For documents without dynamic
+/// parts, otherwise [`Error::Dynamic`] with an entry per dynamic part and its
+/// span is returned.
+///
+/// ```rust
+/// use std::collections::HashSet;
+///
+/// use quote::quote;
+/// use syn_rsx::tokens_to_html;
+///
+/// let void_set: HashSet<_> = vec!["br"].into_iter().collect();
+/// let html = tokens_to_html(quote! { <p>"a"<br />"b"</p> }, &void_set).unwrap();
+///
+/// assert_eq!(html, "<p>a<br>b</p>");
+/// ```
+///
+/// [`proc-macro2::TokenStream`]: https://docs.rs/proc-macro2/latest/proc_macro2/struct.TokenStream.html
+/// [`Error::Dynamic`]: enum.Error.html#variant.Dynamic
+pub fn tokens_to_html(
+    tokens: proc_macro2::TokenStream,
+    void_set: &HashSet<&str>,
+) -> std::result::Result<String, Error> {
+    let nodes = parse2(tokens)?;
+    let error = dynamic_parts(&nodes)
+        .iter()
+        .map(|part| syn::Error::new(part.span(), "expected static HTML, found dynamic part"))
+        .reduce(|mut error, next| {
+            error.combine(next);
+            error
+        });
+    if let Some(error) = error {
+        return Err(Error::Dynamic(error));
+    }
+
+    let mut html = String::new();
+    html::write_html_with_void_set(&mut html, &nodes, void_set);
+
+    Ok(html)
+}
+
+/// Parse the given [`proc-macro2::TokenStream`] into a [`Node`] tree with
+/// custom [`ParserConfig`] and return the [`Warning`]s collected while
+/// parsing, e.g. for [`ParserConfig::enforce_naming_convention`].
+///
+/// [`proc-macro2::TokenStream`]: https://docs.rs/proc-macro2/latest/proc_macro2/struct.TokenStream.html
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+/// [`Warning`]: diagnostic/struct.Warning.html
+/// [`ParserConfig::enforce_naming_convention`]: struct.ParserConfig.html#method.enforce_naming_convention
+pub fn parse2_with_warnings(
+    tokens: proc_macro2::TokenStream,
+    config: ParserConfig,
+) -> Result<(Vec<Node>, Vec<Warning>)> {
+    let parser = Parser::new(config);
+    let nodes = (|input: ParseStream| parser.parse(input)).parse2(tokens)?;
+
+    Ok((nodes, parser.take_warnings()))
+}
+
+/// Parse the given [`proc-macro2::TokenStream`] into a [`Node`] tree with
+/// custom [`ParserConfig`] and return the warnings and errors as
+/// [`SimpleDiagnostic`]s. Unlike [`parse2_with_warnings`], warnings collected
+/// before an error are kept. Use [`diagnostic::partition`] to split them by
+/// level.
+///
+/// Needs the `span-locations` feature.
+///
+/// [`proc-macro2::TokenStream`]: https://docs.rs/proc-macro2/latest/proc_macro2/struct.TokenStream.html
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+/// [`SimpleDiagnostic`]: diagnostic/struct.SimpleDiagnostic.html
+/// [`diagnostic::partition`]: diagnostic/fn.partition.html
+#[cfg(feature = "span-locations")]
+pub fn parse2_with_diagnostics(
+    tokens: proc_macro2::TokenStream,
+    config: ParserConfig,
+) -> (Option<Vec<Node>>, Vec<SimpleDiagnostic>) {
+    let parser = Parser::new(config);
+    let result = (|input: ParseStream| parser.parse(input)).parse2(tokens);
+
+    let mut diagnostics: Vec<_> = parser
+        .take_warnings()
+        .iter()
+        .map(SimpleDiagnostic::from)
+        .collect();
+    match result {
+        Ok(nodes) => (Some(nodes), diagnostics),
+        Err(error) => {
+            let mut errors = SimpleDiagnostic::from_error(&error);
+            parser.attach_suggestions(&mut errors);
+            diagnostics.extend(errors);
+            (None, diagnostics)
+        }
+    }
+}
+
+/// Parse the given [`proc-macro2::TokenStream`] into a [`Node`] tree with
+/// custom [`ParserConfig`] and return the collected [`ParseStats`]. Enables
+/// [`ParserConfig::collect_stats`].
+///
+/// [`proc-macro2::TokenStream`]: https://docs.rs/proc-macro2/latest/proc_macro2/struct.TokenStream.html
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+/// [`ParseStats`]: struct.ParseStats.html
+/// [`ParserConfig::collect_stats`]: struct.ParserConfig.html#method.collect_stats
+pub fn parse2_with_stats(
+    tokens: proc_macro2::TokenStream,
+    config: ParserConfig,
+) -> Result<(Vec<Node>, ParseStats)> {
+    let parser = Parser::new(config.collect_stats(true));
+    let nodes = (|input: ParseStream| parser.parse(input)).parse2(tokens)?;
+    let stats = parser.stats().unwrap_or_default();
+
+    Ok((nodes, stats))
+}
+
+/// Parse the value of the given string literal into a [`Node`] tree, e.g.
+/// for macros like `html!("<div />")`. All nodes get the span of the
+/// literal.
+///
+/// ```rust
+/// use syn::parse_quote;
+/// use syn_rsx::parse_lit_str;
+///
+/// let nodes = parse_lit_str(&parse_quote! { "<a href=\"x\">\"y\"</a>" }).unwrap();
+///
+/// assert_eq!(nodes[0].to_string(), "<a href=\"x\">y</a>");
+/// ```
+///
+/// [`Node`]: struct.Node.html
+pub fn parse_lit_str(lit: &syn::LitStr) -> Result<Vec<Node>> {
+    parse_lit_str_with_config(lit, ParserConfig::default())
+}
+
+/// Parse the value of the given string literal into a [`Node`] tree with
+/// custom [`ParserConfig`].
+///
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+pub fn parse_lit_str_with_config(lit: &syn::LitStr, config: ParserConfig) -> Result<Vec<Node>> {
+    let parser = move |input: ParseStream| Parser::new(config).parse(input);
+
+    lit.parse_with(parser)
+}
+
+/// Parse the given source string into a [`Node`] tree, reporting errors as
+/// [`SimpleDiagnostic`]s. Meant for consumers outside of proc macros, e.g.
+/// CLI tools.
+///
+/// Needs the `span-locations` feature.
+///
+/// [`Node`]: struct.Node.html
+/// [`SimpleDiagnostic`]: diagnostic/struct.SimpleDiagnostic.html
+#[cfg(feature = "span-locations")]
+pub fn parse_str(source: &str) -> std::result::Result<Vec<Node>, Vec<SimpleDiagnostic>> {
+    parse_str_with_config(source, ParserConfig::default())
+}
+
+/// Parse the given source string into a [`Node`] tree with custom
+/// [`ParserConfig`], reporting errors as [`SimpleDiagnostic`]s. Errors like
+/// mismatched close tags come with a [`Suggestion`] to fix them.
+///
+/// Needs the `span-locations` feature.
+///
+/// [`Node`]: struct.Node.html
+/// [`ParserConfig`]: struct.ParserConfig.html
+/// [`SimpleDiagnostic`]: diagnostic/struct.SimpleDiagnostic.html
+/// [`Suggestion`]: diagnostic/struct.Suggestion.html
+#[cfg(feature = "span-locations")]
+pub fn parse_str_with_config(
+    source: &str,
+    config: ParserConfig,
+) -> std::result::Result<Vec<Node>, Vec<SimpleDiagnostic>> {
+    let tokens = source
+        .parse::<proc_macro2::TokenStream>()
+        .map_err(|error| {
+            vec![SimpleDiagnostic::from(&syn::Error::new(
+                error.span(),
+                error.to_string(),
+            ))]
+        })?;
+
+    let parser = Parser::new(config);
+    (|input: ParseStream| parser.parse(input))
+        .parse2(tokens)
+        .map_err(|error| {
+            let mut diagnostics = SimpleDiagnostic::from_error(&error);
+            parser.attach_suggestions(&mut diagnostics);
+            diagnostics
+        })
+}
+
+/// Best-effort parsing for editors with
+/// [`ParserConfig::chunked_recovery`]: split the source at line boundaries
+/// and parse each chunk on its own, so an error like an unclosed brace
+/// doesn't prevent parsing the rest of the source. Returns a result per
+/// chunk, which is why this isn't part of [`parse_str_with_config`]. Without
+/// `chunked_recovery` the whole source is a single chunk.
+///
+/// If the whole source parses, it's a single chunk. Otherwise, starting at
+/// the first line, lines are added to a chunk until it parses. If no chunk
+/// of up to 64 lines starting at a line parses, an error for that line is
+/// returned and parsing continues with the next line. Blank lines are
+/// skipped. Spans keep the line numbers of the whole source.
+///
+/// Limitations:
+/// - Nodes that start or end in the middle of a line can't be split from their
+///   neighbours, e.g. an error in `<a></a><b>` is reported for the whole line.
+/// - An unclosed element makes every chunk starting at its line fail, so its
+///   children are parsed as separate chunks following the error.
+/// - Nodes spanning more than 64 lines are only parsed as a whole if the whole
+///   source parses, else their lines are parsed as separate chunks.
+/// - [`ParserConfig::number_of_top_level_nodes`] and
+///   [`ParserConfig::type_of_top_level_nodes`] are checked per chunk.
+///
+/// ```rust
+/// use syn_rsx::{parse_str_chunked, ParserConfig};
+///
+/// let chunks = parse_str_chunked(
+///     "<div>\n  \"ok\"\n</div>\n<p>{ unclosed</p>\n<span />",
+///     ParserConfig::new().chunked_recovery(true),
+/// );
+///
+/// assert_eq!(chunks.len(), 3);
+/// assert!(chunks[0].is_ok());
+/// assert!(chunks[1].is_err());
+/// assert!(chunks[2].is_ok());
+/// ```
+///
+/// Needs the `span-locations` feature.
+///
+/// [`parse_str_with_config`]: fn.parse_str_with_config.html
+/// [`ParserConfig::chunked_recovery`]: struct.ParserConfig.html#method.chunked_recovery
+/// [`ParserConfig::number_of_top_level_nodes`]: struct.ParserConfig.html#method.number_of_top_level_nodes
+/// [`ParserConfig::type_of_top_level_nodes`]: struct.ParserConfig.html#method.type_of_top_level_nodes
+#[cfg(feature = "span-locations")]
+pub fn parse_str_chunked(
+    source: &str,
+    config: ParserConfig,
+) -> Vec<std::result::Result<Vec<Node>, Vec<SimpleDiagnostic>>> {
+    // Number of lines a chunk may have, so that a line that doesn't parse
+    // costs at most this many parses of at most this many lines.
+    const MAX_CHUNK_LINES: usize = 64;
+
+    if !config.chunked_recovery {
+        return vec![parse_str_with_config(source, config)];
+    }
+
+    let parser = Parser::new(config);
+    let lines = source.split_inclusive('\n').collect::<Vec<_>>();
+
+    // Prefix chunks with newlines so that spans have the line numbers of the
+    // whole source.
+    let parse_lines = |start: usize, end: usize| -> Result<Vec<Node>> {
+        let chunk = "\n".repeat(start) + &lines[start..=end].concat();
+        let tokens = chunk
+            .parse::<proc_macro2::TokenStream>()
+            .map_err(|error| syn::Error::new(error.span(), error.to_string()))?;
+
+        (|input: ParseStream| parser.parse(input)).parse2(tokens)
+    };
+
+    if let Some(Ok(nodes)) = lines.len().checked_sub(1).map(|end| parse_lines(0, end)) {
+        return vec![Ok(nodes)];
+    }
+
+    let mut chunks = vec![];
+    let mut start = 0;
+    while start < lines.len() {
+        if lines[start].trim().is_empty() {
+            start += 1;
+            continue;
+        }
+
+        // The error of the chunk with only the first line is reported if no
+        // chunk parses.
+        let mut line_error = None;
+        let max_end = lines.len().min(start + MAX_CHUNK_LINES);
+        let parsed = (start..max_end).find_map(|end| match parse_lines(start, end) {
+            Ok(nodes) => Some((end, nodes)),
+            Err(error) => {
+                line_error.get_or_insert(error);
+                None
+            }
+        });
+        match (parsed, line_error) {
+            (Some((end, nodes)), _) => {
+                chunks.push(Ok(nodes));
+                start = end + 1;
+            }
+            (None, error) => {
+                chunks.extend(error.map(|error| Err(SimpleDiagnostic::from_error(&error))));
+                start += 1;
+            }
+        }
+    }
+
+    chunks
+}
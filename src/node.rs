@@ -1,19 +1,27 @@
 //! Tree of nodes.
 
-use std::{convert::TryFrom, fmt, ops::Deref};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
 
-use proc_macro2::{Punct, Span, TokenStream};
-use quote::ToTokens;
+use proc_macro2::{Group, Punct, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
+    parse::{ParseStream, Parser as _},
     punctuated::{Pair, Punctuated},
     spanned::Spanned,
-    Expr, ExprBlock, ExprLit, ExprPath, Ident, Lit,
+    visit_mut::{self, VisitMut},
+    Expr, ExprBlock, ExprLit, ExprPath, Ident, Lit, LitInt,
 };
 
-use crate::Error;
+use crate::{ElementClassification, Error, FormatConfig, ParserConfig};
 
 /// Node types.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NodeType {
     Element,
     Attribute,
@@ -43,7 +51,8 @@ impl fmt::Display for NodeType {
 }
 
 /// Node in the tree.
-#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
 pub enum Node {
     Element(NodeElement),
     Attribute(NodeAttribute),
@@ -87,6 +96,184 @@ impl Node {
     }
 }
 
+impl Node {
+    /// Re-emit the node as tokens. Parsing the tokens again yields the same
+    /// tree, except for blocks changed by `transform_block` and flat trees.
+    ///
+    /// Nodes don't implement [`ToTokens`] since that would replace their
+    /// [`Spanned`] implementations.
+    pub fn to_token_stream(&self) -> TokenStream {
+        match self {
+            Node::Element(node) => node.to_token_stream(),
+            Node::Attribute(node) => node.to_token_stream(),
+            Node::Text(node) => node.to_token_stream(),
+            Node::Comment(node) => node.to_token_stream(),
+            Node::Doctype(node) => node.to_token_stream(),
+            Node::Block(node) => node.to_token_stream(),
+            Node::Fragment(node) => node.to_token_stream(),
+        }
+    }
+
+    /// Wrap the given nodes into a [`Node::Fragment`], see
+    /// [`NodeFragment::wrap`].
+    pub fn wrap_fragment(nodes: Vec<Node>) -> Node {
+        Node::Fragment(NodeFragment::wrap(nodes))
+    }
+
+    /// Hash of the node's content, ignoring spans and spacing, e.g. to cache
+    /// generated code. Structurally equal nodes hash equally within the same
+    /// build of the crate.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hash_tokens(self.to_token_stream(), &mut hasher);
+        hasher.finish()
+    }
+
+    /// Re-emit the node as tokens and parse them again with the given
+    /// [`ParserConfig`], e.g. to check that a transform produced valid RSX.
+    ///
+    /// [`ParserConfig`]: struct.ParserConfig.html
+    pub fn reparse(&self, config: &ParserConfig) -> syn::Result<Node> {
+        self.parse_tokens(self.to_token_stream(), config.clone())
+    }
+
+    /// Deep clone the node with all spans set to the given span, e.g. to emit
+    /// a parsed subtree multiple times without duplicate spans.
+    pub fn respanned(&self, span: Span) -> Node {
+        let mut node = self.clone();
+        node.respan(span);
+        node
+    }
+
+    /// Set all spans of the node and its descendants to the given span.
+    fn respan(&mut self, span: Span) {
+        match self {
+            Node::Element(element) => {
+                respan_name(&mut element.name, span);
+                element.span = span;
+                if let Some(slash) = &mut element.self_closing_slash {
+                    *slash = span;
+                }
+                for node in element.attributes.iter_mut().chain(&mut element.children) {
+                    node.respan(span);
+                }
+            }
+            Node::Attribute(attribute) => {
+                respan_name(&mut attribute.key, span);
+                if let Some(value) = &mut attribute.value {
+                    respan_expr(value, span);
+                }
+                attribute.span = span;
+                if let Some(eq_span) = &mut attribute.eq_span {
+                    *eq_span = span;
+                }
+                for part in attribute.template.iter_mut().flatten() {
+                    if let TemplatePart::Expr(ident) = part {
+                        ident.set_span(span);
+                    }
+                }
+            }
+            Node::Text(text) => respan_expr(&mut text.value, span),
+            Node::Comment(comment) => {
+                respan_expr(&mut comment.value, span);
+                comment.span = span;
+            }
+            Node::Doctype(doctype) => {
+                respan_expr(&mut doctype.value, span);
+                doctype.span = span;
+                doctype.keyword.set_span(span);
+                if let Some(group) = &mut doctype.internal_subset {
+                    let mut respanned = Group::new(
+                        group.delimiter(),
+                        crate::parser::respan(group.stream(), span),
+                    );
+                    respanned.set_span(span);
+                    *group = respanned;
+                }
+            }
+            Node::Block(block) => respan_expr(&mut block.value, span),
+            Node::Fragment(fragment) => {
+                fragment.span = span;
+                for child in &mut fragment.children {
+                    child.respan(span);
+                }
+            }
+        }
+    }
+
+    /// Re-emit the node as tokens, cleaned up according to the given
+    /// [`FormatConfig`], e.g. with lowercase element names.
+    ///
+    /// ```rust
+    /// use quote::quote;
+    /// use syn_rsx::{parse2, FormatConfig};
+    ///
+    /// let nodes = parse2(quote! { <DIV class=""></DIV> }).unwrap();
+    /// let config = FormatConfig::new()
+    ///     .lowercase_names(true)
+    ///     .drop_empty_attributes(true)
+    ///     .self_close_empty(true);
+    ///
+    /// assert_eq!(
+    ///     nodes[0].to_formatted_tokens(&config).to_string(),
+    ///     quote! { <div /> }.to_string()
+    /// );
+    /// ```
+    pub fn to_formatted_tokens(&self, config: &FormatConfig) -> TokenStream {
+        match self {
+            Node::Element(element) => {
+                let name = if config.lowercase_names && element.name.as_block().is_none() {
+                    lowercase_idents(element.name.to_token_stream())
+                } else {
+                    element.name.to_token_stream()
+                };
+                let attributes: TokenStream = element
+                    .attributes
+                    .iter()
+                    .filter(|attribute| {
+                        !(config.drop_empty_attributes && is_empty_attribute(attribute))
+                    })
+                    .map(Node::to_token_stream)
+                    .collect();
+
+                if element.is_self_closing()
+                    || (config.self_close_empty && element.children.is_empty())
+                {
+                    quote_spanned! { element.span => <#name #attributes /> }
+                } else {
+                    let children: TokenStream = element
+                        .children
+                        .iter()
+                        .map(|child| child.to_formatted_tokens(config))
+                        .collect();
+                    quote_spanned! { element.span => <#name #attributes> #children </#name> }
+                }
+            }
+            Node::Fragment(fragment) => {
+                let children: TokenStream = fragment
+                    .children
+                    .iter()
+                    .map(|child| child.to_formatted_tokens(config))
+                    .collect();
+                quote_spanned! { fragment.span => <> #children </> }
+            }
+            _ => self.to_token_stream(),
+        }
+    }
+
+    fn parse_tokens(&self, tokens: TokenStream, config: ParserConfig) -> syn::Result<Node> {
+        let parser = crate::Parser::new(config);
+
+        match self {
+            Node::Attribute(_) => (|input: ParseStream| parser.attribute(input)).parse2(tokens),
+            _ => {
+                let mut nodes = (|input: ParseStream| parser.node(input)).parse2(tokens)?;
+                Ok(nodes.remove(0))
+            }
+        }
+    }
+}
+
 impl Spanned for Node {
     fn span(&self) -> Span {
         match self {
@@ -101,21 +288,19 @@ impl Spanned for Node {
     }
 }
 
+/// Best-effort HTML-like output, e.g. for debugging. Blocks are rendered as
+/// their Rust tokens and self-closing tags are not preserved.
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Element(_) => "Node::Element",
-                Self::Attribute(_) => "Node::Attribute",
-                Self::Text(_) => "Node::Text",
-                Self::Comment(_) => "Node::Comment",
-                Self::Doctype(_) => "Node::Doctype",
-                Self::Block(_) => "Node::Block",
-                Self::Fragment(_) => "Node::Fragment",
-            }
-        )
+        match self {
+            Self::Element(node) => node.fmt(f),
+            Self::Attribute(node) => node.fmt(f),
+            Self::Text(node) => node.fmt(f),
+            Self::Comment(node) => node.fmt(f),
+            Self::Doctype(node) => node.fmt(f),
+            Self::Block(node) => node.fmt(f),
+            Self::Fragment(node) => node.fmt(f),
+        }
     }
 }
 
@@ -123,7 +308,7 @@ impl fmt::Display for Node {
 ///
 /// A HTMLElement tag, with optional children and attributes.
 /// Potentially selfclosing. Any tag name is valid.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeElement {
     /// Name of the element.
     pub name: NodeName,
@@ -136,11 +321,206 @@ pub struct NodeElement {
     /// Note: This should cover the entire node in nightly, but is a "close
     /// enough" approximation in stable until [Span::join] is stabilized.
     pub span: Span,
+    pub(crate) self_closing_slash: Option<Span>,
+    pub(crate) template: bool,
+    pub(crate) foreign_content: bool,
+    pub(crate) classification: ElementClassification,
+}
+
+impl NodeElement {
+    /// Create a `NodeElement` from its parts.
+    pub fn from_parts(
+        name: NodeName,
+        attributes: Vec<Node>,
+        children: Vec<Node>,
+        span: Span,
+    ) -> Self {
+        Self {
+            name,
+            attributes,
+            children,
+            span,
+            self_closing_slash: None,
+            template: false,
+            foreign_content: false,
+            classification: ElementClassification::Normal,
+        }
+    }
+
+    /// Move the parts out of the `NodeElement`, the counterpart to
+    /// [`NodeElement::from_parts`].
+    pub fn into_parts(self) -> (NodeName, Vec<Node>, Vec<Node>, Span) {
+        (self.name, self.attributes, self.children, self.span)
+    }
+
+    /// Whether the element was written as self-closing tag, e.g. `<div />`.
+    pub fn is_self_closing(&self) -> bool {
+        self.self_closing_slash.is_some()
+    }
+
+    /// Span of the `/` in a self-closing tag like `<div />`, e.g. to point
+    /// diagnostics at it.
+    pub fn self_closing_slash_span(&self) -> Option<Span> {
+        self.self_closing_slash
+    }
+
+    /// Whether the element is one of the configured
+    /// [`ParserConfig::template_elements`], meaning its children are inert
+    /// template content.
+    ///
+    /// [`ParserConfig::template_elements`]: struct.ParserConfig.html#method.template_elements
+    pub fn is_template(&self) -> bool {
+        self.template
+    }
+
+    /// Whether the element is one of the configured
+    /// [`ParserConfig::foreign_content_elements`] or a descendant of one, e.g.
+    /// SVG content where self-closing tags and the case of names matter when
+    /// rendering HTML.
+    ///
+    /// [`ParserConfig::foreign_content_elements`]: struct.ParserConfig.html#method.foreign_content_elements
+    pub fn is_foreign_content(&self) -> bool {
+        self.foreign_content
+    }
+
+    /// How the children of the element were parsed according to
+    /// [`ParserConfig::element_classification`], e.g. to tell that the text
+    /// of an [`ElementClassification::EscapableRawText`] element may contain
+    /// character references.
+    ///
+    /// [`ParserConfig::element_classification`]: struct.ParserConfig.html#method.element_classification
+    pub fn classification(&self) -> ElementClassification {
+        self.classification
+    }
+
+    /// Get the text child of an element classified as
+    /// [`ElementClassification::RawText`], e.g. the body of a `<script>`.
+    /// Returns `None` for other elements and raw text elements without
+    /// exactly one child.
+    pub fn raw_text_child(&self) -> Option<&NodeText> {
+        match (self.classification, self.children.as_slice()) {
+            (ElementClassification::RawText, [Node::Text(text)]) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Concatenated text of all descendant text nodes, like the DOM's
+    /// `textContent`. Blocks are skipped since their value is dynamic.
+    pub fn text_content(&self) -> String {
+        let mut content = String::new();
+        push_text_content(&self.children, &mut content);
+        content
+    }
+
+    /// Replace the children and return the previous ones, e.g. in tree
+    /// transforms.
+    pub fn replace_children(&mut self, children: Vec<Node>) -> Vec<Node> {
+        std::mem::replace(&mut self.children, children)
+    }
+
+    /// Append a child.
+    pub fn push_child(&mut self, child: Node) {
+        self.children.push(child);
+    }
+
+    /// Whether the element has no children other than whitespace-only text,
+    /// e.g. to remove redundant `<div></div>`. Unlike void elements, empty
+    /// elements still have a close tag.
+    pub fn is_empty(&self) -> bool {
+        self.children.iter().all(|child| match child {
+            Node::Text(text) => String::try_from(&text.value)
+                .map(|text| text.trim().is_empty())
+                .unwrap_or(false),
+            _ => false,
+        })
+    }
+
+    /// Whether the element has the given name, e.g. `div` or `ui::Button`.
+    /// Close tags always match the open tag, since mismatched tags are a
+    /// parse error.
+    pub fn name_matches(&self, name: &str) -> bool {
+        self.name.to_string() == name
+    }
+
+    /// Get the source spans of the attributes, in source order.
+    ///
+    /// Can be used to compute the gaps between attributes, e.g. when
+    /// formatting.
+    pub fn attribute_spans(&self) -> Vec<Span> {
+        self.attributes.iter().map(Spanned::span).collect()
+    }
+
+    /// Iterate over the attributes with keyed attributes, spreads and other
+    /// block attributes told apart, e.g. for code generation.
+    pub fn attribute_entries(&self) -> impl Iterator<Item = AttributeEntry<'_>> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Node::Attribute(attribute) => Some(AttributeEntry::Keyed {
+                    key: &attribute.key,
+                    value: attribute.value.as_ref().map(|value| value.as_ref()),
+                }),
+                Node::Block(block) => Some(match crate::tree::spread_expr(&block.value) {
+                    Some(expr) => AttributeEntry::Spread(expr),
+                    None => AttributeEntry::Block(block),
+                }),
+                _ => None,
+            })
+    }
+
+    /// Map the keys of the keyed attributes to their values, e.g. for quick
+    /// lookups in static analysis. Block attributes like `{props}` are left
+    /// out. For duplicate keys the last attribute wins.
+    pub fn attributes_map(&self) -> HashMap<String, Option<&Expr>> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Node::Attribute(attribute) => Some((
+                    attribute.key.to_string(),
+                    attribute.value.as_ref().map(|value| value.as_ref()),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl fmt::Display for NodeElement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeElement")
+        write!(f, "<{}", self.name)?;
+        for attribute in &self.attributes {
+            write!(f, " {}", attribute)?;
+        }
+        write!(f, ">")?;
+        for child in &self.children {
+            child.fmt(f)?;
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+impl NodeElement {
+    /// Re-emit the element as tokens. Elements classified as
+    /// [`ElementClassification::Void`] that aren't self-closing are emitted
+    /// without close tag, e.g. `<br>`.
+    pub fn to_token_stream(&self) -> TokenStream {
+        let name = &self.name;
+        let attributes = self.attributes_tokens();
+
+        if self.is_self_closing() {
+            quote_spanned! { self.span => <#name #attributes /> }
+        } else if self.classification == ElementClassification::Void {
+            quote_spanned! { self.span => <#name #attributes> }
+        } else {
+            let children = nodes_to_tokens(&self.children);
+            quote_spanned! { self.span => <#name #attributes> #children </#name> }
+        }
+    }
+
+    /// Re-emit only the attributes as tokens, without the name and the
+    /// brackets of the tag, e.g. to forward them to another macro.
+    pub fn attributes_tokens(&self) -> TokenStream {
+        nodes_to_tokens(&self.attributes)
     }
 }
 
@@ -150,10 +530,60 @@ impl Spanned for NodeElement {
     }
 }
 
+/// Attribute of an element, see [`NodeElement::attribute_entries`].
+#[derive(Debug)]
+pub enum AttributeEntry<'a> {
+    /// Keyed attribute with optional value, e.g. `key="value"` or `key`.
+    Keyed {
+        key: &'a NodeName,
+        value: Option<&'a Expr>,
+    },
+    /// Block attribute, e.g. `{props}`.
+    Block(&'a NodeBlock),
+    /// Spread, e.g. `props` of `{..props}`.
+    Spread(&'a Expr),
+}
+
+/// Naming convention of event handler attributes, see
+/// [`NodeAttribute::as_event_handler`].
+///
+/// [`NodeAttribute::as_event_handler`]: struct.NodeAttribute.html#method.as_event_handler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventStyle {
+    /// Prefix separated by a colon, e.g. `on:click` for `Colon("on")`.
+    Colon(&'static str),
+    /// Prefix of a single identifier, e.g. `onclick` or `onClick` for
+    /// `Prefix("on")`. The event name is lowercased.
+    Prefix(&'static str),
+}
+
+/// Event handler attribute, see [`NodeAttribute::as_event_handler`].
+///
+/// [`NodeAttribute::as_event_handler`]: struct.NodeAttribute.html#method.as_event_handler
+#[derive(Debug)]
+pub struct EventHandler<'a> {
+    /// Name of the event, e.g. `click`.
+    pub name: String,
+    /// Value of the attribute.
+    pub handler: &'a NodeValueExpr,
+}
+
+/// Part of a string attribute value with placeholders, see
+/// [`NodeAttribute::template`].
+///
+/// [`NodeAttribute::template`]: struct.NodeAttribute.html#method.template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplatePart {
+    /// Literal text, with `{{` and `}}` unescaped to braces.
+    Literal(String),
+    /// Placeholder, e.g. `name` of `{name}`.
+    Expr(Ident),
+}
+
 /// Attribute node.
 ///
 /// Attributes of opening tags. Every attribute is itself a node.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeAttribute {
     /// Key of the element attribute.
     pub key: NodeName,
@@ -164,11 +594,186 @@ pub struct NodeAttribute {
     /// Note: This should cover the entire node in nightly, but is a "close
     /// enough" approximation in stable until [Span::join] is stabilized.
     pub span: Span,
+    pub(crate) eq_span: Option<Span>,
+    pub(crate) value_list: bool,
+    pub(crate) template: Option<Vec<TemplatePart>>,
+    pub(crate) directive: bool,
+    pub(crate) negated: bool,
+}
+
+impl NodeAttribute {
+    /// Create a `NodeAttribute` from its key, value and span.
+    pub fn new(key: NodeName, value: Option<NodeValueExpr>, span: Span) -> Self {
+        Self {
+            key,
+            value,
+            span,
+            eq_span: None,
+            value_list: false,
+            template: None,
+            directive: false,
+            negated: false,
+        }
+    }
+
+    /// Get the values of a comma separated value list, e.g. `a` and `b` of
+    /// `class=a, b`, see [`ParserConfig::comma_separated_attribute_values`].
+    /// The list is stored as [`Expr::Tuple`] in `value`.
+    ///
+    /// [`ParserConfig::comma_separated_attribute_values`]: struct.ParserConfig.html#method.comma_separated_attribute_values
+    pub fn value_list(&self) -> Option<Vec<&Expr>> {
+        match self.value.as_ref().map(|value| value.as_ref()) {
+            Some(Expr::Tuple(tuple)) if self.value_list => Some(tuple.elems.iter().collect()),
+            _ => None,
+        }
+    }
+
+    /// Get the parts of a string value with placeholders, e.g. `"Hi "`,
+    /// `name` and `"!"` of `title="Hi {name}!"`, see
+    /// [`ParserConfig::interpolate_string_attributes`]. The string literal is
+    /// kept in `value`.
+    ///
+    /// [`ParserConfig::interpolate_string_attributes`]: struct.ParserConfig.html#method.interpolate_string_attributes
+    pub fn template(&self) -> Option<&[TemplatePart]> {
+        self.template.as_deref()
+    }
+
+    /// Whether the key is prefixed with `!`, e.g. `<input !checked />`, see
+    /// [`ParserConfig::allow_attribute_negation`].
+    ///
+    /// [`ParserConfig::allow_attribute_negation`]: struct.ParserConfig.html#method.allow_attribute_negation
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Span of the value expression, e.g. `foo()` in `x = foo()`.
+    pub fn value_span(&self) -> Option<Span> {
+        self.value.as_ref().map(|value| value.span())
+    }
+
+    /// Span of the `=` and the value expression, e.g. `= foo()` in
+    /// `x = foo()`. Falls back to the span of the value where spans can't be
+    /// joined.
+    pub fn eq_value_span(&self) -> Option<Span> {
+        let value_span = self.value_span()?;
+        Some(
+            self.eq_span
+                .and_then(|eq_span| eq_span.join(value_span))
+                .unwrap_or(value_span),
+        )
+    }
+
+    /// Get the event name and handler if the attribute is an event handler in
+    /// one of the given styles, e.g. `click` for `on:click={handler}` with
+    /// [`EventStyle::Colon("on")`]. Attributes without value are not event
+    /// handlers.
+    ///
+    /// [`EventStyle::Colon("on")`]: enum.EventStyle.html#variant.Colon
+    pub fn as_event_handler(&self, styles: &[EventStyle]) -> Option<EventHandler<'_>> {
+        let handler = self.value.as_ref()?;
+        let key = self.key.to_string();
+
+        styles.iter().find_map(|style| {
+            let name = match (style, &self.key) {
+                (EventStyle::Colon(prefix), NodeName::Punctuated(_)) => key
+                    .strip_prefix(prefix)
+                    .and_then(|name| name.strip_prefix(':'))
+                    .map(str::to_owned),
+                (EventStyle::Prefix(prefix), NodeName::Path(_)) => {
+                    key.strip_prefix(prefix).map(str::to_lowercase)
+                }
+                _ => None,
+            }?;
+
+            if name.is_empty() {
+                return None;
+            }
+
+            Some(EventHandler { name, handler })
+        })
+    }
+
+    /// Split a directive like `class:active` into `(class, active)` if its
+    /// prefix is one of the [`ParserConfig::attribute_directives`].
+    ///
+    /// [`ParserConfig::attribute_directives`]: struct.ParserConfig.html#method.attribute_directives
+    pub fn directive(&self) -> Option<(Ident, Ident)> {
+        if !self.directive {
+            return None;
+        }
+
+        match &self.key {
+            NodeName::Punctuated(name) if name.len() == 2 => {
+                let mut pairs = name.pairs();
+                match (pairs.next(), pairs.next()) {
+                    (
+                        Some(Pair::Punctuated(NodeNameFragment::Ident(directive), punct)),
+                        Some(Pair::End(NodeNameFragment::Ident(arg))),
+                    ) if punct.as_char() == ':' => Some((directive.clone(), arg.clone())),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the value is a raw string literal like `r#"..."#`, e.g. to
+    /// re-emit it the same way.
+    pub fn value_is_raw_string(&self) -> bool {
+        match self.value.as_ref().map(AsRef::as_ref) {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            })) => lit_str.token().to_string().starts_with('r'),
+            _ => false,
+        }
+    }
+
+    /// Whether the value was wrapped in braces like `key={value}`, e.g. for
+    /// serializers that distinguish it from `key=value`. Braced values are
+    /// stored as [`Expr::Block`] and re-emitted with their braces.
+    pub fn value_is_braced(&self) -> bool {
+        matches!(
+            self.value.as_ref().map(AsRef::as_ref),
+            Some(Expr::Block(ExprBlock { label: None, .. }))
+        )
+    }
 }
 
 impl fmt::Display for NodeAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeAttribute")
+        write!(f, "{}", self.key)?;
+        match &self.value {
+            Some(value) => match value.as_ref() {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) => write!(f, "=\"{}\"", lit_str.value()),
+                Expr::Block(_) => write!(f, "={}", value.to_token_stream()),
+                _ => write!(f, "={{{}}}", value.to_token_stream()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl NodeAttribute {
+    /// Re-emit the attribute as tokens.
+    pub fn to_token_stream(&self) -> TokenStream {
+        let key = &self.key;
+        let key = if self.negated {
+            quote_spanned! { self.span => !#key }
+        } else {
+            quote! { #key }
+        };
+        match self.value.as_ref().map(|value| value.as_ref()) {
+            Some(Expr::Tuple(tuple)) if self.value_list => {
+                let elems = &tuple.elems;
+                quote_spanned! { self.span => #key = #elems }
+            }
+            Some(value) => quote_spanned! { self.span => #key = #value },
+            None => quote! { #key },
+        }
     }
 }
 
@@ -185,15 +790,73 @@ impl Spanned for NodeAttribute {
 /// with nightly rust.
 ///
 /// [planned to support unquoted text]: https://github.com/stoically/syn-rsx/issues/2
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeText {
     /// The text value.
     pub value: NodeValueExpr,
 }
 
+impl NodeText {
+    /// Get the literal as written in the source, including quotes and
+    /// escapes, e.g. `"a\nb"`. Use `String::try_from(&text.value)` to get the
+    /// value with escapes resolved.
+    pub fn raw_literal(&self) -> String {
+        self.value.to_token_stream().to_string()
+    }
+
+    /// Get the value of a string literal with the common leading whitespace
+    /// of its non-blank lines removed, e.g. for multiline text that's
+    /// indented relative to the template. Whitespace-only lines become empty.
+    /// Returns `None` for other literals.
+    pub fn dedented(&self) -> Option<String> {
+        let value = match self.value.as_ref() {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => lit_str.value(),
+            _ => return None,
+        };
+
+        // The indent is counted in chars, since whitespace can be wider than
+        // a byte, e.g. a no-break space.
+        let indent = value
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.chars().take_while(|char| char.is_whitespace()).count())
+            .min()
+            .unwrap_or(0);
+
+        let mut dedented = value
+            .lines()
+            .map(|line| {
+                let start = line
+                    .char_indices()
+                    .take_while(|(_, char)| char.is_whitespace())
+                    .take(indent)
+                    .last()
+                    .map_or(0, |(index, char)| index + char.len_utf8());
+                line[start..].trim_end_matches(char::is_whitespace)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if value.ends_with('\n') {
+            dedented.push('\n');
+        }
+
+        Some(dedented)
+    }
+}
+
 impl fmt::Display for NodeText {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeText")
+        write_value(f, &self.value)
+    }
+}
+
+impl NodeText {
+    /// Re-emit the text as tokens.
+    pub fn to_token_stream(&self) -> TokenStream {
+        self.value.to_token_stream()
     }
 }
 
@@ -207,7 +870,7 @@ impl Spanned for NodeText {
 ///
 /// Comment: `<!-- "comment" -->`, currently has the same restrictions as
 /// `Text` (comment needs to be quoted).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeComment {
     /// The comment value.
     pub value: NodeValueExpr,
@@ -220,7 +883,24 @@ pub struct NodeComment {
 
 impl fmt::Display for NodeComment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeComment")
+        write!(f, "<!-- ")?;
+        write_value(f, &self.value)?;
+        write!(f, " -->")
+    }
+}
+
+impl NodeComment {
+    /// Get the text of the comment without the `<!--` and `-->` delimiters,
+    /// the value of string literals and other literals as written, e.g. `x`
+    /// for `<!-- "x" -->` and `1` for `<!-- 1 -->`.
+    pub fn text(&self) -> String {
+        String::try_from(&self.value).unwrap_or_else(|_| self.value.to_token_stream().to_string())
+    }
+
+    /// Re-emit the comment as tokens.
+    pub fn to_token_stream(&self) -> TokenStream {
+        let value = &self.value;
+        quote_spanned! { self.span => <!-- #value --> }
     }
 }
 
@@ -234,7 +914,7 @@ impl Spanned for NodeComment {
 ///
 /// Doctype declaration: `<!DOCTYPE html>` (case insensitive), `html` is the
 /// node value in this case.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeDoctype {
     /// The doctype value.
     pub value: NodeValueExpr,
@@ -243,11 +923,56 @@ pub struct NodeDoctype {
     /// Note: This should cover the entire node in nightly, but is a "close
     /// enough" approximation in stable until [Span::join] is stabilized.
     pub span: Span,
+    pub(crate) keyword: Ident,
+    pub(crate) internal_subset: Option<Group>,
+}
+
+impl NodeDoctype {
+    /// Create a `NodeDoctype` with the given value, e.g. `html`.
+    pub fn new(value: NodeValueExpr, span: Span) -> Self {
+        Self {
+            value,
+            span,
+            keyword: Ident::new("DOCTYPE", span),
+            internal_subset: None,
+        }
+    }
+
+    /// Get the bracketed internal subset of the doctype, e.g.
+    /// `[<!ENTITY x "y">]` in `<!DOCTYPE root [<!ENTITY x "y">]>`.
+    pub fn internal_subset(&self) -> Option<&Group> {
+        self.internal_subset.as_ref()
+    }
+
+    /// Get the doctype value with surrounding whitespace trimmed and inner
+    /// whitespace collapsed to single spaces, e.g. `html`.
+    pub fn normalized_value(&self) -> String {
+        String::try_from(&self.value)
+            .unwrap_or_else(|_| self.value.to_token_stream().to_string())
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl fmt::Display for NodeDoctype {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeDoctype")
+        write!(f, "<!DOCTYPE ")?;
+        write_value(f, &self.value)?;
+        if let Some(internal_subset) = &self.internal_subset {
+            write!(f, " {}", internal_subset)?;
+        }
+        write!(f, ">")
+    }
+}
+
+impl NodeDoctype {
+    /// Re-emit the doctype as tokens, keeping the casing of `DOCTYPE`.
+    pub fn to_token_stream(&self) -> TokenStream {
+        let keyword = &self.keyword;
+        let value = &self.value;
+        let internal_subset = &self.internal_subset;
+        quote_spanned! { self.span => <!#keyword #value #internal_subset> }
     }
 }
 
@@ -260,7 +985,7 @@ impl Spanned for NodeDoctype {
 /// Fragement node.
 ///
 /// Fragment: `<></>`
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeFragment {
     /// Children of the fragment node.
     pub children: Vec<Node>,
@@ -273,7 +998,37 @@ pub struct NodeFragment {
 
 impl fmt::Display for NodeFragment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeFragment")
+        for child in &self.children {
+            child.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl NodeFragment {
+    /// Wrap the given nodes into a fragment with call-site span, e.g. to get a
+    /// single root node.
+    pub fn wrap(children: Vec<Node>) -> NodeFragment {
+        NodeFragment {
+            children,
+            span: Span::call_site(),
+        }
+    }
+
+    /// Replace the children and return the previous ones.
+    pub fn replace_children(&mut self, children: Vec<Node>) -> Vec<Node> {
+        std::mem::replace(&mut self.children, children)
+    }
+
+    /// Append a child.
+    pub fn push_child(&mut self, child: Node) {
+        self.children.push(child);
+    }
+
+    /// Re-emit the fragment as tokens.
+    pub fn to_token_stream(&self) -> TokenStream {
+        let children = nodes_to_tokens(&self.children);
+        quote_spanned! { self.span => <> #children </> }
     }
 }
 
@@ -286,7 +1041,7 @@ impl Spanned for NodeFragment {
 /// Block node.
 ///
 /// Arbitrary rust code in braced `{}` blocks.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeBlock {
     /// The block value..
     pub value: NodeValueExpr,
@@ -294,7 +1049,23 @@ pub struct NodeBlock {
 
 impl fmt::Display for NodeBlock {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "NodeBlock")
+        write!(f, "{}", self.value.to_token_stream())
+    }
+}
+
+impl NodeBlock {
+    /// Re-emit the block as tokens.
+    pub fn to_token_stream(&self) -> TokenStream {
+        self.value.to_token_stream()
+    }
+
+    /// Span of the braces of the block, e.g. to highlight block boundaries.
+    /// Transformed blocks have the span of the original braces.
+    pub fn brace_span(&self) -> Span {
+        match self.value.as_ref() {
+            Expr::Block(expr) => expr.block.brace_token.span,
+            expr => expr.span(),
+        }
     }
 }
 
@@ -304,8 +1075,129 @@ impl Spanned for NodeBlock {
     }
 }
 
+fn is_empty_attribute(node: &Node) -> bool {
+    match node {
+        Node::Attribute(attribute) => attribute
+            .value
+            .as_ref()
+            .and_then(|value| String::try_from(value).ok())
+            .is_some_and(|value| value.is_empty()),
+        _ => false,
+    }
+}
+
+/// Lowercase all identifiers, keeping raw identifiers raw.
+fn lowercase_idents(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string().to_lowercase();
+                TokenTree::Ident(match name.strip_prefix("r#") {
+                    Some(name) => Ident::new_raw(name, ident.span()),
+                    None => Ident::new(&name, ident.span()),
+                })
+            }
+            token => token,
+        })
+        .collect()
+}
+
+fn push_text_content(nodes: &[Node], content: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => match String::try_from(&text.value) {
+                Ok(value) => content.push_str(&value),
+                Err(_) => content.push_str(&text.value.to_token_stream().to_string()),
+            },
+            Node::Element(element) => push_text_content(&element.children, content),
+            Node::Fragment(fragment) => push_text_content(&fragment.children, content),
+            _ => (),
+        }
+    }
+}
+
+/// Set all spans of the name to the given span.
+fn respan_name(name: &mut NodeName, span: Span) {
+    match name {
+        NodeName::Path(path) => Respan(span).visit_expr_path_mut(path),
+        NodeName::Punctuated(punctuated) => {
+            for mut pair in punctuated.pairs_mut() {
+                match pair.value_mut() {
+                    NodeNameFragment::Ident(ident) => ident.set_span(span),
+                    NodeNameFragment::Literal(literal) => literal.set_span(span),
+                }
+                if let Some(punct) = pair.punct_mut() {
+                    punct.set_span(span);
+                }
+            }
+        }
+        NodeName::Block(expr) => respan_expr(expr, span),
+    }
+}
+
+/// Set all spans of the expression to the given span.
+fn respan_expr(expr: &mut Expr, span: Span) {
+    Respan(span).visit_expr_mut(expr);
+}
+
+/// Visitor that sets every span it reaches to the given span, including the
+/// spans of literals and of unparsed tokens in macros, attributes and
+/// verbatim expressions.
+struct Respan(Span);
+
+impl VisitMut for Respan {
+    fn visit_span_mut(&mut self, span: &mut Span) {
+        *span = self.0;
+    }
+
+    fn visit_lit_mut(&mut self, lit: &mut Lit) {
+        lit.set_span(self.0);
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        visit_mut::visit_macro_mut(self, mac);
+        mac.tokens = crate::parser::respan(std::mem::take(&mut mac.tokens), self.0);
+    }
+
+    fn visit_attribute_mut(&mut self, attr: &mut syn::Attribute) {
+        visit_mut::visit_attribute_mut(self, attr);
+        attr.tokens = crate::parser::respan(std::mem::take(&mut attr.tokens), self.0);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Verbatim(tokens) => {
+                *tokens = crate::parser::respan(std::mem::take(tokens), self.0)
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+}
+
+/// Hash delimiters, punctuation characters, identifiers and literals.
+fn hash_tokens(tokens: TokenStream, hasher: &mut DefaultHasher) {
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                format!("{:?}", group.delimiter()).hash(hasher);
+                hash_tokens(group.stream(), hasher);
+                ")".hash(hasher);
+            }
+            TokenTree::Punct(punct) => punct.as_char().hash(hasher),
+            TokenTree::Ident(ident) => ident.to_string().hash(hasher),
+            TokenTree::Literal(literal) => literal.to_string().hash(hasher),
+        }
+    }
+}
+
+/// Re-emit the given nodes as tokens, in order.
+pub(crate) fn nodes_to_tokens(nodes: &[Node]) -> TokenStream {
+    nodes.iter().map(Node::to_token_stream).collect()
+}
+
 /// Name of the node.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum NodeName {
     /// A plain identifier like `div` is a path of length 1, e.g. `<div />`. Can
     /// be separated by double colons, e.g. `<foo::bar />`.
@@ -313,12 +1205,80 @@ pub enum NodeName {
 
     /// Name separated by punctuation, e.g. `<div data-foo="bar" />` or `<div
     /// data:foo="bar" />`.
-    Punctuated(Punctuated<Ident, Punct>),
+    Punctuated(Punctuated<NodeNameFragment, Punct>),
 
     /// Arbitrary rust code in braced `{}` blocks.
     Block(Expr),
 }
 
+/// Segment of a [`NodeName::Punctuated`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodeNameFragment {
+    /// Identifier, e.g. `data` in `data-foo`.
+    Ident(Ident),
+    /// Integer literal after a dash, e.g. `5` in `x-5` or `2x` in `grid-2x`.
+    Literal(LitInt),
+}
+
+impl From<Ident> for NodeNameFragment {
+    fn from(ident: Ident) -> Self {
+        NodeNameFragment::Ident(ident)
+    }
+}
+
+impl From<LitInt> for NodeNameFragment {
+    fn from(literal: LitInt) -> Self {
+        NodeNameFragment::Literal(literal)
+    }
+}
+
+impl ToTokens for NodeNameFragment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            NodeNameFragment::Ident(ident) => ident.to_tokens(tokens),
+            NodeNameFragment::Literal(literal) => literal.to_tokens(tokens),
+        }
+    }
+}
+
+impl fmt::Display for NodeNameFragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeNameFragment::Ident(ident) => ident.fmt(f),
+            NodeNameFragment::Literal(literal) => literal.token().fmt(f),
+        }
+    }
+}
+
+impl NodeName {
+    /// Whether the name looks like a component instead of an HTML element,
+    /// i.e. a path with multiple segments like `ui::Button` or a name
+    /// starting with an uppercase letter like `Button`.
+    pub fn is_component(&self) -> bool {
+        match self {
+            NodeName::Path(expr) => {
+                expr.path.segments.len() > 1
+                    || expr
+                        .path
+                        .segments
+                        .first()
+                        .and_then(|segment| segment.ident.to_string().chars().next())
+                        .is_some_and(char::is_uppercase)
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the expression of a `NodeName::Block`, e.g. to generate code for a
+    /// dynamic tag name.
+    pub fn as_block(&self) -> Option<&Expr> {
+        match self {
+            NodeName::Block(expr) => Some(expr),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<&NodeName> for ExprBlock {
     type Error = Error;
 
@@ -400,7 +1360,7 @@ impl fmt::Display for NodeName {
 }
 
 /// Smart pointer to `syn::Expr`.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NodeValueExpr {
     expr: Expr,
 }
@@ -426,6 +1386,18 @@ impl Deref for NodeValueExpr {
     }
 }
 
+impl AsMut<Expr> for NodeValueExpr {
+    fn as_mut(&mut self) -> &mut Expr {
+        &mut self.expr
+    }
+}
+
+impl DerefMut for NodeValueExpr {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.expr
+    }
+}
+
 impl From<Expr> for NodeValueExpr {
     fn from(expr: Expr) -> Self {
         Self { expr }
@@ -444,6 +1416,12 @@ impl From<ExprBlock> for NodeValueExpr {
     }
 }
 
+impl ToTokens for NodeValueExpr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.expr.to_tokens(tokens);
+    }
+}
+
 impl From<NodeValueExpr> for Expr {
     fn from(value: NodeValueExpr) -> Self {
         value.expr
@@ -493,7 +1471,7 @@ impl TryFrom<&NodeValueExpr> for String {
                 Lit::Str(lit_str) => Some(lit_str.value()),
                 _ => None,
             },
-            Expr::Path(expr) => Some(path_to_string(&expr)),
+            Expr::Path(expr) => Some(path_to_string(expr)),
             _ => None,
         }
         .ok_or_else(|| {
@@ -504,6 +1482,15 @@ impl TryFrom<&NodeValueExpr> for String {
     }
 }
 
+/// Write string literals and paths as plain string, anything else as Rust
+/// tokens.
+fn write_value(f: &mut fmt::Formatter<'_>, value: &NodeValueExpr) -> fmt::Result {
+    match String::try_from(value) {
+        Ok(value) => write!(f, "{}", value),
+        Err(_) => write!(f, "{}", value.to_token_stream()),
+    }
+}
+
 fn path_to_string(expr: &ExprPath) -> String {
     expr.path
         .segments
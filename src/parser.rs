@@ -1,44 +1,256 @@
 //! RSX Parser
 
-use std::vec;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    str::FromStr,
+    vec,
+};
 
-use proc_macro2::{Punct, Span, TokenStream, TokenTree};
+#[cfg(feature = "span-locations")]
+use proc_macro2::LineColumn;
+use proc_macro2::{Group, Punct, Span, TokenStream, TokenTree};
+use quote::quote_spanned;
 use syn::{
     braced,
     ext::IdentExt,
-    parse::{discouraged::Speculative, Parse, ParseStream, Parser as _, Peek},
+    parse::{discouraged::Speculative, Parse, ParseBuffer, ParseStream, Parser as _, Peek},
     punctuated::Punctuated,
     spanned::Spanned,
-    token::{Brace, Colon, Colon2},
-    Block, Error, Expr, ExprBlock, ExprLit, ExprPath, Ident, Path, PathSegment, Result, Token,
+    token::{Brace, Bracket, Colon, Colon2, Paren},
+    Block, Error, Expr, ExprBlock, ExprLit, ExprPath, ExprTuple, Ident, Lit, LitInt, LitStr, Path,
+    PathSegment, Result, Stmt, Token,
 };
 
-use crate::{config::TransformBlockFn, node::*, punctuation::*, ParserConfig};
+#[cfg(feature = "span-locations")]
+use crate::diagnostic::{SimpleDiagnostic, Suggestion};
+use crate::{
+    config::TransformBlockFn, diagnostic::Warning, html::ELEMENT_NAMES, node::*, punctuation::*,
+    ElementClassification, InterpolationSyntax, ParserConfig,
+};
 
 /// RSX Parser
 pub struct Parser {
     config: ParserConfig,
+    /// Whether the element that is currently parsed is foreign content.
+    foreign_content: Cell<bool>,
+    /// Names of the elements enclosing the element that is currently parsed.
+    ancestors: RefCell<Vec<String>>,
+    warnings: RefCell<Vec<Warning>>,
+    /// Errors with a [`Suggestion`] to fix them.
+    #[cfg(feature = "span-locations")]
+    suggestions: RefCell<Vec<SimpleDiagnostic>>,
+    stats: ParseStatsCounter,
+    original_tokens: RefCell<Option<TokenStream>>,
+    /// Events recorded for [`Parser::parse_events`].
+    events: RefCell<Option<Vec<Event>>>,
+}
+
+/// SAX-style event of a parse, see [`Parser::parse_events`].
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// Doctype, e.g. `<!DOCTYPE html>`.
+    Doctype(NodeDoctype),
+    /// Open tag of an element, followed by the events of its children.
+    OpenTag {
+        /// Name of the element.
+        name: NodeName,
+        /// Attributes of the element, including default attributes.
+        attributes: Vec<Node>,
+        /// Span of the open tag.
+        span: Span,
+    },
+    /// Close tag of an element, also emitted for self-closing and void
+    /// elements.
+    CloseTag {
+        /// Name of the element.
+        name: NodeName,
+        /// Span of the close tag, or of the open tag if there's none.
+        span: Span,
+    },
+    /// Open tag of a fragment.
+    OpenFragment(Span),
+    /// Close tag of a fragment, or the open tag of an unclosed fragment.
+    CloseFragment(Span),
+    /// Text.
+    Text(NodeText),
+    /// Comment.
+    Comment(NodeComment),
+    /// Block, e.g. `{value}`.
+    Block(NodeBlock),
+}
+
+/// Statistics about a parse, see [`ParserConfig::collect_stats`].
+///
+/// [`ParserConfig::collect_stats`]: struct.ParserConfig.html#method.collect_stats
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Number of parsed nodes, including attributes.
+    pub nodes: usize,
+    /// Number of forks of the input that were taken to look ahead.
+    pub forks: usize,
+    /// Number of tokens in the input, including tokens inside groups.
+    pub tokens: usize,
+}
+
+#[derive(Default)]
+struct ParseStatsCounter {
+    nodes: Cell<usize>,
+    forks: Cell<usize>,
+    tokens: Cell<usize>,
 }
 
 impl Parser {
     /// Create a new parser with the given [`ParserConfig`].
     pub fn new(config: ParserConfig) -> Parser {
-        Parser { config }
+        Parser {
+            config,
+            foreign_content: Cell::new(false),
+            ancestors: RefCell::new(vec![]),
+            warnings: RefCell::new(vec![]),
+            #[cfg(feature = "span-locations")]
+            suggestions: RefCell::new(vec![]),
+            stats: ParseStatsCounter::default(),
+            original_tokens: RefCell::new(None),
+            events: RefCell::new(None),
+        }
+    }
+
+    /// Get the statistics collected while parsing if
+    /// [`ParserConfig::collect_stats`] is enabled.
+    ///
+    /// [`ParserConfig::collect_stats`]: struct.ParserConfig.html#method.collect_stats
+    pub fn stats(&self) -> Option<ParseStats> {
+        if !self.config.collect_stats {
+            return None;
+        }
+
+        Some(ParseStats {
+            nodes: self.stats.nodes.get(),
+            forks: self.stats.forks.get(),
+            tokens: self.stats.tokens.get(),
+        })
+    }
+
+    /// Fork the stream and count the fork for [`ParseStats`].
+    fn fork<'a>(&self, input: ParseStream<'a>) -> ParseBuffer<'a> {
+        if self.config.collect_stats {
+            self.stats.forks.set(self.stats.forks.get() + 1);
+        }
+
+        input.fork()
+    }
+
+    /// Count parsed nodes for [`ParseStats`].
+    fn count_nodes(&self, count: usize) {
+        if self.config.collect_stats {
+            self.stats.nodes.set(self.stats.nodes.get() + count);
+        }
+    }
+
+    /// Get the tokens of the input last given to [`Parser::parse`], e.g. to
+    /// skip structural work in incremental setups when the input of two
+    /// parses is the same. Only kept with
+    /// [`ParserConfig::retain_original_tokens`].
+    ///
+    /// [`ParserConfig::retain_original_tokens`]: struct.ParserConfig.html#method.retain_original_tokens
+    pub fn original_tokens(&self) -> Option<TokenStream> {
+        self.original_tokens.borrow().clone()
+    }
+
+    /// Take the [`Warning`]s collected while parsing.
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.warnings.take()
+    }
+
+    /// Attach the [`Suggestion`]s collected while parsing to the matching
+    /// error diagnostics.
+    #[cfg(feature = "span-locations")]
+    pub(crate) fn attach_suggestions(&self, diagnostics: &mut [SimpleDiagnostic]) {
+        let suggestions = self.suggestions.borrow();
+        for diagnostic in diagnostics {
+            diagnostic.suggestion = suggestions
+                .iter()
+                .find(|suggestion| {
+                    suggestion.message == diagnostic.message
+                        && suggestion.start == diagnostic.start
+                        && suggestion.end == diagnostic.end
+                })
+                .and_then(|suggestion| suggestion.suggestion.clone());
+        }
     }
 
     /// Parse a given [`ParseStream`].
+    ///
+    /// The returned nodes are in source order, which includes the position of
+    /// top level comments relative to the doctype.
+    ///
+    /// Parsing either consumes the entire input or returns an error, tokens
+    /// that can't be parsed as nodes are never left over silently.
     pub fn parse(&self, input: ParseStream) -> Result<Vec<Node>> {
+        if self.config.collect_stats {
+            let tokens = self.stats.tokens.get() + count_tokens(input.cursor().token_stream());
+            self.stats.tokens.set(tokens);
+        }
+        if self.config.retain_original_tokens {
+            self.original_tokens
+                .replace(Some(input.cursor().token_stream()));
+        }
+
         let mut nodes = vec![];
         let mut top_level_nodes = 0;
+        let mut content_nodes = 0;
+        let mut root = false;
+        let mut content = false;
         while !input.cursor().eof() {
             let mut parsed_nodes = self.node(input)?;
 
-            if let Some(type_of_top_level_nodes) = &self.config.type_of_top_level_nodes {
-                if &parsed_nodes[0].r#type() != type_of_top_level_nodes {
-                    return Err(input.error(format!(
-                        "top level nodes need to be of type {}",
-                        type_of_top_level_nodes
-                    )));
+            if let (Some(type_of_top_level_nodes), Some(node)) =
+                (&self.config.type_of_top_level_nodes, parsed_nodes.first())
+            {
+                if &node.r#type() != type_of_top_level_nodes {
+                    return Err(self.error(
+                        input,
+                        format!(
+                            "top level nodes need to be of type {}",
+                            type_of_top_level_nodes
+                        ),
+                    ));
+                }
+            }
+
+            if self.config.single_root_element {
+                if let Some(node) = parsed_nodes.first() {
+                    self.check_single_root(node, &mut root)?;
+                }
+            }
+
+            if self.config.validate_doctype_position {
+                match parsed_nodes.first() {
+                    Some(node @ Node::Doctype(_)) if content => {
+                        return Err(Error::new(
+                            node.span(),
+                            "doctype needs to be at the start of the document",
+                        ));
+                    }
+                    Some(Node::Comment(_) | Node::Doctype(_)) | None => (),
+                    Some(_) => content = true,
+                }
+            }
+
+            if self.config.error_on_trailing_content {
+                match parsed_nodes.first() {
+                    Some(Node::Comment(_) | Node::Doctype(_)) | None => (),
+                    Some(node) => {
+                        let expected = self.config.number_of_top_level_nodes.unwrap_or(1);
+                        if content_nodes == expected {
+                            return Err(Error::new(
+                                node.span(),
+                                format!("unexpected content after {} top level nodes", expected),
+                            ));
+                        }
+                        content_nodes += 1;
+                    }
                 }
             }
 
@@ -46,23 +258,192 @@ impl Parser {
             nodes.append(&mut parsed_nodes);
         }
 
+        if self.config.single_root_element && !root {
+            return Err(self.error(input, "expected a single root element"));
+        }
+
         if let Some(number_of_top_level_nodes) = &self.config.number_of_top_level_nodes {
             if &top_level_nodes != number_of_top_level_nodes {
-                return Err(input.error(format!(
-                    "saw {} top level nodes but exactly {} are required",
-                    top_level_nodes, number_of_top_level_nodes
-                )));
+                return Err(self.error(
+                    input,
+                    format!(
+                        "saw {} top level nodes but exactly {} are required",
+                        top_level_nodes, number_of_top_level_nodes
+                    ),
+                ));
             }
         }
 
         Ok(nodes)
     }
 
+    /// Parse a given [`ParseStream`] into a sequence of [`Event`]s instead of
+    /// a tree, e.g. for transformation pipelines. The events are recorded by
+    /// the parse routines as they go, so they're in source order and an
+    /// element's children come between its [`Event::OpenTag`] and
+    /// [`Event::CloseTag`].
+    ///
+    /// ```rust
+    /// use quote::quote;
+    /// use syn::parse::Parser as _;
+    /// use syn_rsx::{Event, Parser, ParserConfig};
+    ///
+    /// let parser = Parser::new(ParserConfig::new());
+    /// let events: Vec<_> = (|input: syn::parse::ParseStream| parser.parse_events(input))
+    ///     .parse2(quote! { <div>"a"</div> })
+    ///     .unwrap()
+    ///     .collect();
+    ///
+    /// assert!(matches!(
+    ///     events.as_slice(),
+    ///     [
+    ///         Event::OpenTag { .. },
+    ///         Event::Text(_),
+    ///         Event::CloseTag { .. }
+    ///     ]
+    /// ));
+    /// ```
+    pub fn parse_events(&self, input: ParseStream) -> Result<impl Iterator<Item = Event>> {
+        self.events.replace(Some(vec![]));
+        let result = self.parse(input);
+        let events = self.events.take().unwrap_or_default();
+        result?;
+
+        Ok(events.into_iter())
+    }
+
+    /// Record an [`Event`] if [`Parser::parse_events`] is running.
+    fn emit(&self, event: impl FnOnce() -> Event) {
+        if let Some(events) = self.events.borrow_mut().as_mut() {
+            events.push(event());
+        }
+    }
+
+    /// Record the [`Event`] of a node without children. Elements and
+    /// fragments record their own events while their children are parsed.
+    fn emit_leaf(&self, node: &Node) {
+        let mut events = self.events.borrow_mut();
+        let Some(events) = events.as_mut() else { return };
+        events.push(match node {
+            Node::Doctype(doctype) => Event::Doctype(doctype.clone()),
+            Node::Text(text) => Event::Text(text.clone()),
+            Node::Comment(comment) => Event::Comment(comment.clone()),
+            Node::Block(block) => Event::Block(block.clone()),
+            Node::Element(_) | Node::Attribute(_) | Node::Fragment(_) => return,
+        });
+    }
+
+    /// Collect a [`Warning`] without stopping parsing.
+    fn warn<T: Display>(&self, span: Span, message: T) {
+        self.warnings.borrow_mut().push(Warning {
+            message: message.to_string(),
+            span,
+            #[cfg(feature = "span-locations")]
+            suggestion: None,
+        });
+    }
+
+    /// Collect a [`Warning`] with a [`Suggestion`] to remove the span.
+    fn warn_removable<T: Display>(&self, span: Span, message: T) {
+        self.warnings.borrow_mut().push(Warning {
+            message: message.to_string(),
+            span,
+            #[cfg(feature = "span-locations")]
+            suggestion: Some(Suggestion {
+                start: span.start(),
+                end: span.end(),
+                replacement: String::new(),
+            }),
+        });
+    }
+
+    /// Record a [`Suggestion`] to replace the source from the start of
+    /// `start` to the end of `end`, which fixes the error. Suggestions are
+    /// only recorded with the `span-locations` feature.
+    #[cfg_attr(not(feature = "span-locations"), allow(unused_variables))]
+    fn suggest<T: Display>(&self, error: Error, start: Span, end: Span, replacement: T) -> Error {
+        #[cfg(feature = "span-locations")]
+        self.record_suggestion(&error, start.start(), end.end(), replacement.to_string());
+
+        error
+    }
+
+    /// Record a [`Suggestion`] to insert the text after the span, which fixes
+    /// the error.
+    #[cfg_attr(not(feature = "span-locations"), allow(unused_variables))]
+    fn suggest_insert<T: Display>(&self, error: Error, after: Span, text: T) -> Error {
+        #[cfg(feature = "span-locations")]
+        self.record_suggestion(&error, after.end(), after.end(), text.to_string());
+
+        error
+    }
+
+    /// Record a [`Suggestion`] to replace the source from the closing `>` of
+    /// the open tag to the end of `end` with the text and the `>`, which
+    /// fixes the error.
+    #[cfg_attr(not(feature = "span-locations"), allow(unused_variables))]
+    fn suggest_tag_end(&self, error: Error, tag_open_span: Span, end: Span, text: String) -> Error {
+        #[cfg(feature = "span-locations")]
+        {
+            let mut start = tag_open_span.end();
+            start.column = start.column.saturating_sub(1);
+            self.record_suggestion(&error, start, end.end(), format!(" {}>", text));
+        }
+
+        error
+    }
+
+    #[cfg(feature = "span-locations")]
+    fn record_suggestion(
+        &self,
+        error: &Error,
+        start: LineColumn,
+        end: LineColumn,
+        replacement: String,
+    ) {
+        let mut diagnostic = SimpleDiagnostic::from(error);
+        diagnostic.suggestion = Some(Suggestion {
+            start,
+            end,
+            replacement,
+        });
+        self.suggestions.borrow_mut().push(diagnostic);
+    }
+
+    /// Create an error at the current position of the stream. At the end of
+    /// the input the [`ParserConfig::call_site_span`] is used if it's set.
+    fn error<T: Display>(&self, input: ParseStream, message: T) -> Error {
+        match self.config.call_site_span {
+            Some(span) if input.is_empty() => Error::new(span, message),
+            _ => input.error(message),
+        }
+    }
+
+    /// Check that the top level node is allowed next to the root element seen
+    /// so far. Comments and doctypes are allowed anywhere.
+    fn check_single_root(&self, node: &Node, root: &mut bool) -> Result<()> {
+        match node {
+            Node::Comment(_) | Node::Doctype(_) => Ok(()),
+            Node::Element(_) | Node::Fragment(_) if !*root => {
+                *root = true;
+                Ok(())
+            }
+            Node::Element(_) | Node::Fragment(_) => Err(Error::new(
+                node.span(),
+                "expected a single root element, wrap the elements in a fragment",
+            )),
+            _ => Err(Error::new(
+                node.span(),
+                format!("expected a single root element, found {}", node.r#type()),
+            )),
+        }
+    }
+
     /// Parse the next [`Node`] in the tree.
     ///
     /// To improve performance it peeks the next 1-3 tokens and calls the
     /// according node parser function depending on that.
-    fn node(&self, input: ParseStream) -> Result<Vec<Node>> {
+    pub(crate) fn node(&self, input: ParseStream) -> Result<Vec<Node>> {
         let mut node = if input.peek(Token![<]) {
             if input.peek2(Token![!]) {
                 if input.peek3(Ident) {
@@ -77,9 +458,19 @@ impl Parser {
             }
         } else if input.peek(Brace) {
             self.block(input)
+        } else if self.config.detect_misplaced_attributes && self.is_attribute(input) {
+            return Err(self.misplaced_attribute(input, None));
+        } else if let (Some(syntax), true) = (self.config.text_interpolation, input.peek(LitStr)) {
+            // Text and blocks have no children, so there's nothing to flatten.
+            let nodes = self.interpolated_text(input, syntax)?;
+            nodes.iter().for_each(|node| self.emit_leaf(node));
+            self.count_nodes(nodes.len());
+            return Ok(nodes);
         } else {
             self.text(input)
         }?;
+        self.emit_leaf(&node);
+        self.count_nodes(1);
 
         if self.config.flat_tree {
             let mut children = node
@@ -97,45 +488,172 @@ impl Parser {
         }
     }
 
+    /// Check whether the stream starts with a keyed attribute like `key=`.
+    fn is_attribute(&self, input: ParseStream) -> bool {
+        let fork = &self.fork(input);
+        self.node_name(fork).is_ok() && fork.peek(Token![=])
+    }
+
+    /// Create the error for an attribute that is outside of the open tag. If
+    /// it directly follows the open tag the error gets a [`Suggestion`] to
+    /// move it into the open tag.
+    fn misplaced_attribute(&self, input: ParseStream, tag_open_span: Option<Span>) -> Error {
+        let error = self.error(
+            input,
+            "attribute is outside of the open tag, move it before the closing `>`",
+        );
+        let Some(tag_open_span) = tag_open_span else {
+            return error;
+        };
+
+        // The value isn't parsed as expression, since the following nodes
+        // would be taken as part of it, e.g. `"x" < /div>`.
+        let fork = &self.fork(input);
+        let value = (|| {
+            self.node_name(fork).ok()?;
+            fork.parse::<Token![=]>().ok()?;
+            fork.parse::<TokenTree>().ok()
+        })();
+        let attribute = value.and_then(|value| input.span().join(value.span()));
+        match attribute.and_then(|span| Some((span, span.source_text()?))) {
+            Some((span, text)) => self.suggest_tag_end(error, tag_open_span, span, text),
+            None => error,
+        }
+    }
+
     /// Parse the stream as [`Node::Text`].
     fn text(&self, input: ParseStream) -> Result<Node> {
-        let value = input.parse::<ExprLit>()?.into();
+        Ok(self.text_node(input.parse::<ExprLit>()?))
+    }
+
+    /// Parse the stream as string literal and split it into [`Node::Text`]
+    /// and [`Node::Block`] at interpolations.
+    fn interpolated_text(
+        &self,
+        input: ParseStream,
+        syntax: InterpolationSyntax,
+    ) -> Result<Vec<Node>> {
+        let lit = input.parse::<LitStr>()?;
+        let span = lit.span();
+        let value = lit.value();
+        if !value.contains(syntax.open()) {
+            return Ok(vec![self.text_node(ExprLit {
+                attrs: vec![],
+                lit: lit.into(),
+            })]);
+        }
+
+        let mut nodes = vec![];
+        let mut rest = value.as_str();
+        while let Some(start) = rest.find(syntax.open()) {
+            let (text, interpolation) = rest.split_at(start);
+            let interpolation = &interpolation[syntax.open().len()..];
+            let end = interpolation_end(interpolation, syntax)
+                .ok_or_else(|| Error::new(span, "interpolation is not closed"))?;
+
+            if !text.is_empty() {
+                nodes.push(self.text_node(ExprLit {
+                    attrs: vec![],
+                    lit: LitStr::new(text, span).into(),
+                }));
+            }
+
+            let tokens = TokenStream::from_str(&interpolation[..end])
+                .map_err(|error| Error::new(span, error))?;
+            let tokens = respan(tokens, span);
+            let value = syn::parse2::<ExprBlock>(quote_spanned!(span=> { #tokens }))?;
+            nodes.push(Node::Block(NodeBlock {
+                value: Expr::Block(value).into(),
+            }));
+
+            rest = &interpolation[end + syntax.close().len()..];
+        }
+
+        if !rest.is_empty() {
+            nodes.push(self.text_node(ExprLit {
+                attrs: vec![],
+                lit: LitStr::new(rest, span).into(),
+            }));
+        }
+
+        Ok(nodes)
+    }
+
+    /// Create a [`Node::Text`] and apply the `transform_text` callback.
+    fn text_node(&self, value: ExprLit) -> Node {
+        let mut text = NodeText {
+            value: Expr::Lit(value).into(),
+        };
+
+        if let Some(transform_fn) = &self.config.transform_text {
+            if let Some(transformed) = transform_fn(&text) {
+                let lit = LitStr::new(&transformed, text.value.span());
+                text.value = NodeValueExpr::new(
+                    ExprLit {
+                        attrs: vec![],
+                        lit: lit.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
 
-        Ok(Node::Text(NodeText { value }))
+        Node::Text(text)
     }
 
     /// Parse the stream as [`Node::Block`].
     fn block(&self, input: ParseStream) -> Result<Node> {
-        let value = if let Some(transform_fn) = &self.config.transform_block {
-            self.block_transform(input, transform_fn)?
-        } else {
-            self.block_expr(input)?
+        let value = match &self.config.transform_block {
+            Some(transform_fn) if self.block_transform_applies(input)? => {
+                self.block_transform(input, transform_fn.as_ref())?
+            }
+            _ => self.block_expr(input)?,
         }
         .into();
 
         Ok(Node::Block(NodeBlock { value }))
     }
 
+    /// Check whether the `transform_block_if` predicate, if any, wants the
+    /// next block to be transformed. Only peeks into the block content.
+    fn block_transform_applies(&self, input: ParseStream) -> Result<bool> {
+        match &self.config.transform_block_if {
+            Some(predicate) => {
+                let fork = self.fork(input);
+                let content;
+                braced!(content in fork);
+                Ok(predicate(&content))
+            }
+            None => Ok(true),
+        }
+    }
+
     /// Replace the next [`TokenTree::Group`] in the given parse stream with a
     /// token stream returned by a user callback, or parse as original block if
     /// no token stream is returned.
     fn block_transform(&self, input: ParseStream, transform_fn: &TransformBlockFn) -> Result<Expr> {
-        let parser = move |block_content: ParseStream| {
-            let forked_block_content = block_content.fork();
-
-            match transform_fn(&forked_block_content) {
-                Ok(transformed_tokens) => match transformed_tokens {
-                    Some(tokens) => {
-                        let parser = move |input: ParseStream| {
-                            Ok(self.block_content_to_block(input, block_content.span()))
-                        };
-                        let transformed_content = parser.parse2(tokens)?;
-                        block_content.advance_to(&forked_block_content);
-                        transformed_content
-                    }
-                    None => self.block_content_to_block(block_content, block_content.span()),
-                },
-                Err(error) => Err(error),
+        // Transformed blocks keep the span of the original braces.
+        let parser = move |brace_span: Span| {
+            move |block_content: ParseStream| {
+                let forked_block_content = self.fork(block_content);
+
+                match transform_fn(&forked_block_content) {
+                    Ok(transformed_tokens) => match transformed_tokens {
+                        Some(mut tokens) => {
+                            if self.config.respan_transformed_blocks {
+                                tokens = respan(tokens, brace_span);
+                            }
+                            let parser = move |input: ParseStream| {
+                                Ok(self.block_content_to_block(input, brace_span))
+                            };
+                            let transformed_content = parser.parse2(tokens)?;
+                            block_content.advance_to(&forked_block_content);
+                            transformed_content
+                        }
+                        None => self.block_content_to_block(block_content, brace_span),
+                    },
+                    Err(error) => Err(error),
+                }
             }
         };
 
@@ -145,7 +663,10 @@ impl Parser {
                 .ok_or_else(|| cursor.error("unexpected: no TokenTree found"))?;
 
             match tree {
-                TokenTree::Group(block_group) => Ok((parser.parse2(block_group.stream())?, next)),
+                TokenTree::Group(block_group) => Ok((
+                    parser(block_group.span()).parse2(block_group.stream())?,
+                    next,
+                )),
                 _ => Err(cursor.error("unexpected: no Group in TokenTree found")),
             }
         })
@@ -166,7 +687,7 @@ impl Parser {
 
     /// Parse the given stream as [`Expr::Block`].
     fn block_expr(&self, input: ParseStream) -> Result<Expr> {
-        let fork = input.fork();
+        let fork = self.fork(input);
         let content;
         let brace_token = braced!(content in fork);
         let block = ExprBlock {
@@ -184,76 +705,336 @@ impl Parser {
 
     /// Parse the given stream as [`NodeElement`].
     fn element(&self, input: ParseStream) -> Result<Node> {
-        let fork = &input.fork();
+        let fork = &self.fork(input);
 
-        if self.tag_close(&input.fork()).is_ok() {
-            return Err(fork.error("close tag has no corresponding open tag"));
+        if self.tag_close(&self.fork(input)).is_ok() {
+            return Err(self.error(fork, "close tag has no corresponding open tag"));
         }
-        let (name, attributes, self_closing, mut span) = self.tag_open(fork)?;
+        let (name, mut attributes, self_closing_slash, mut span) = self.tag_open(fork)?;
+        // Stringifying a name allocates, so it's done once for all lookups.
+        let name_string = name.to_string();
+        if let Some(allowed_elements) = &self.config.allowed_elements {
+            if !allowed_elements.contains(name_string.as_str()) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("element `{}` is not allowed", name),
+                ));
+            }
+        }
+        if self.config.enforce_naming_convention {
+            self.check_naming_convention(&name);
+        }
+        let classify = |name: &str| {
+            self.config
+                .element_classification
+                .get(name)
+                .copied()
+                .unwrap_or(ElementClassification::Normal)
+        };
+        let classification = if self.config.case_insensitive_element_matching {
+            classify(&name_string.to_lowercase())
+        } else {
+            classify(&name_string)
+        };
+
+        self.add_default_attributes(&name, &name_string, &mut attributes)?;
+        self.emit(|| Event::OpenTag {
+            name: name.clone(),
+            attributes: attributes.clone(),
+            span,
+        });
+        let mut close_span = span;
+
+        let foreign_content = self.foreign_content.get()
+            || self
+                .config
+                .foreign_content_elements
+                .contains(name_string.as_str());
 
         let mut children = vec![];
-        if !self_closing {
-            loop {
-                if !self.element_has_children(&name, fork)? {
-                    break;
+        if classification == ElementClassification::Void {
+            // Void elements have no children, but a matching close tag is accepted.
+            if self_closing_slash.is_none() {
+                let close_fork = &self.fork(fork);
+                if let Ok((tag_close_name, closing_span)) = self.tag_close(close_fork) {
+                    if tag_close_name == name {
+                        fork.advance_to(close_fork);
+                        span = span.join(closing_span).unwrap_or(span);
+                        close_span = closing_span;
+                        if self.config.warn_redundant_void_close {
+                            self.warn_removable(
+                                closing_span,
+                                format!("unnecessary close tag of void element `{}`", name),
+                            );
+                        }
+                    }
                 }
-
-                children.append(&mut self.node(fork)?);
             }
+        } else if self_closing_slash.is_none() {
+            // Descendants of foreign content elements are foreign content as well.
+            let parent_foreign_content = self.foreign_content.replace(foreign_content);
+            let nesting = !self.config.forbidden_nesting.is_empty();
+            if nesting {
+                self.ancestors.borrow_mut().push(name_string.clone());
+            }
+            let result = self.element_children(&name, span, fork);
+            if nesting {
+                self.ancestors.borrow_mut().pop();
+            }
+            self.foreign_content.set(parent_foreign_content);
+            children = result?;
 
             let (_, closing_span) = self.tag_close(fork)?;
             span = span.join(closing_span).unwrap_or(span);
+            close_span = closing_span;
         };
+        self.check_classified_children(&name, classification, &children)?;
+        self.check_nesting(&name_string, span);
+        self.emit(|| Event::CloseTag {
+            name: name.clone(),
+            span: close_span,
+        });
 
         input.advance_to(fork);
+        let template = self.config.template_elements.contains(name_string.as_str());
+
         Ok(Node::Element(NodeElement {
             name,
             attributes,
             children,
             span,
+            self_closing_slash,
+            template,
+            foreign_content,
+            classification,
         }))
     }
 
+    /// Parse the children of an element until its close tag.
+    fn element_children(
+        &self,
+        name: &NodeName,
+        tag_open_span: Span,
+        input: ParseStream,
+    ) -> Result<Vec<Node>> {
+        let mut children = vec![];
+        loop {
+            let last_span = children.last().map_or(tag_open_span, Node::span);
+            if !self.element_has_children(name, last_span, input)? {
+                break;
+            }
+            if children.is_empty()
+                && self.config.detect_misplaced_attributes
+                && self.is_attribute(input)
+            {
+                return Err(self.misplaced_attribute(input, Some(tag_open_span)));
+            }
+
+            children.append(&mut self.node(input)?);
+        }
+
+        Ok(children)
+    }
+
+    /// Warn about names of components that aren't PascalCase and PascalCase
+    /// names of HTML elements.
+    fn check_naming_convention(&self, name: &NodeName) {
+        let NodeName::Path(path) = name else {
+            return;
+        };
+        let Some(last) = path.path.segments.last() else {
+            return;
+        };
+        let last = last.ident.to_string();
+
+        if name.is_component() {
+            if last.starts_with(char::is_lowercase) {
+                self.warn(
+                    name.span(),
+                    format!("component `{}` should start with an uppercase letter", name),
+                );
+            } else if path.path.segments.len() == 1
+                && ELEMENT_NAMES.contains(&last.to_lowercase().as_str())
+            {
+                self.warn(
+                    name.span(),
+                    format!(
+                        "`{}` is ambiguous with the HTML element `{}`",
+                        name,
+                        last.to_lowercase()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Add the [`ParserConfig::default_attributes`] of the element that it
+    /// doesn't have yet.
+    ///
+    /// [`ParserConfig::default_attributes`]: struct.ParserConfig.html#method.default_attributes
+    fn add_default_attributes(
+        &self,
+        name: &NodeName,
+        name_string: &str,
+        attributes: &mut Vec<Node>,
+    ) -> Result<()> {
+        let Some(defaults) = self.config.default_attributes.get(name_string) else {
+            return Ok(());
+        };
+
+        for (key, value) in defaults {
+            let exists = attributes.iter().any(|attribute| {
+                matches!(attribute, Node::Attribute(attribute) if attribute.key.to_string() == *key)
+            });
+            if exists {
+                continue;
+            }
+
+            let tokens = TokenStream::from_str(key)
+                .map_err(|error| Error::new(name.span(), error.to_string()))?;
+            let key = (|input: ParseStream| self.node_name(input))
+                .parse2(tokens)
+                .map_err(|error| Error::new(name.span(), error.to_string()))?;
+            // Defaults are checked like written attributes, but errors point
+            // at the element since the defaults have no source.
+            self.check_allowed_attribute(&key)
+                .map_err(|error| Error::new(name.span(), error.to_string()))?;
+            if let Some(max) = self.config.max_attributes_per_element {
+                if attributes.len() == max {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("elements can't have more than {} attributes", max),
+                    ));
+                }
+            }
+            let value = Expr::Lit(ExprLit {
+                attrs: vec![],
+                lit: LitStr::new(value, Span::call_site()).into(),
+            });
+            attributes.push(Node::Attribute(NodeAttribute::new(
+                key,
+                Some(NodeValueExpr::new(value)),
+                Span::call_site(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Warn if an enclosing element forbids elements with the given name.
+    fn check_nesting(&self, name: &str, span: Span) {
+        if self.config.forbidden_nesting.is_empty() {
+            return;
+        }
+
+        let ancestors = self.ancestors.borrow();
+        for (ancestor, descendant) in &self.config.forbidden_nesting {
+            if *descendant == name && ancestors.iter().any(|a| a == ancestor) {
+                self.warn(
+                    span,
+                    format!("`<{}>` must not be nested inside `<{}>`", name, ancestor),
+                );
+            }
+        }
+    }
+
+    /// Check that the children are allowed in elements of the given
+    /// [`ElementClassification`].
+    fn check_classified_children(
+        &self,
+        name: &NodeName,
+        classification: ElementClassification,
+        children: &[Node],
+    ) -> Result<()> {
+        for child in children {
+            match (classification, child) {
+                (ElementClassification::RawText, Node::Text(_)) => (),
+                (ElementClassification::RawText, _) => {
+                    return Err(Error::new(
+                        child.span(),
+                        format!("raw text element `{}` can only contain text", name),
+                    ));
+                }
+                (ElementClassification::EscapableRawText, Node::Text(_) | Node::Block(_)) => (),
+                (ElementClassification::EscapableRawText, _) => {
+                    return Err(Error::new(
+                        child.span(),
+                        format!("element `{}` can only contain text and blocks", name),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check whether the next token in the stream is a closing tag to decide
-    /// whether the node element has children.
-    fn element_has_children(&self, tag_open_name: &NodeName, input: ParseStream) -> Result<bool> {
+    /// whether the node element has children. `last_span` is the span of the
+    /// last child or the open tag, after which a missing close tag is
+    /// suggested.
+    fn element_has_children(
+        &self,
+        tag_open_name: &NodeName,
+        last_span: Span,
+        input: ParseStream,
+    ) -> Result<bool> {
         // An empty input at this point means the tag wasn't closed.
         if input.is_empty() {
-            return Err(Error::new(
+            let error = Error::new(
                 tag_open_name.span(),
                 "open tag has no corresponding close tag and is not self-closing",
-            ));
+            );
+            return Err(self.suggest_insert(error, last_span, format!("</{}>", tag_open_name)));
         }
 
-        if let Ok((tag_close_name, _)) = self.tag_close(&input.fork()) {
+        if let Ok((tag_close_name, _)) = self.tag_close(&self.fork(input)) {
             if tag_open_name == &tag_close_name {
                 // If the next token is a matching close tag then there are no child nodes.
                 return Ok(false);
+            } else if tag_open_name.as_block().is_some() && tag_close_name.as_block().is_some() {
+                // Block names can't be nested elements, so a different block is a mismatch.
+                return Err(Error::new(
+                    tag_close_name.span(),
+                    "close tag block name does not match open tag block name",
+                ));
             } else {
                 // If the next token is a closing tag with a different name it's an invalid
                 // tree.
-                return Err(input.error("close tag has no corresponding open tag"));
+                let error = self.error(input, "close tag has no corresponding open tag");
+                let span = tag_close_name.span();
+                return Err(self.suggest(error, span, span, tag_open_name));
             }
         }
 
+        if let Ok(span) = self.fragment_close(&self.fork(input)) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "expected close tag `</{}>`, found fragment close tag `</>`",
+                    tag_open_name
+                ),
+            ));
+        }
+
         Ok(true)
     }
 
     /// Parse the stream as opening or self-closing tag and extract its
     /// attributes.
-    fn tag_open(&self, input: ParseStream) -> Result<(NodeName, Vec<Node>, bool, Span)> {
+    fn tag_open(&self, input: ParseStream) -> Result<(NodeName, Vec<Node>, Option<Span>, Span)> {
         let span_start = input.span();
         input.parse::<Token![<]>()?;
         let name = self.node_name(input)?;
 
         let mut attributes = TokenStream::new();
-        let (self_closing, span_end) = loop {
+        let (self_closing_slash, span_end) = loop {
             if let Ok(end) = self.tag_open_end(input) {
                 break end;
             }
 
             if input.is_empty() {
-                return Err(input.error("expected closing caret >"));
+                return Err(self.error(input, "expected closing caret >"));
             }
 
             let next: TokenTree = input.parse()?;
@@ -269,18 +1050,18 @@ impl Parser {
 
         let span = span_start.join(span_end).unwrap_or(name.span());
 
-        Ok((name, attributes, self_closing, span))
+        Ok((name, attributes, self_closing_slash, span))
     }
 
     /// Check whether an element tag ended or is self-closing.
-    fn tag_open_end(&self, input: ParseStream) -> Result<(bool, Span)> {
+    fn tag_open_end(&self, input: ParseStream) -> Result<(Option<Span>, Span)> {
         let span_start = input.span();
-        let self_closing = input.parse::<Option<Token![/]>>()?.is_some();
+        let self_closing_slash = input.parse::<Option<Token![/]>>()?.map(|slash| slash.span);
         let span_end = input.span();
         input.parse::<Token![>]>()?;
         let span = span_start.join(span_end).unwrap_or(span_start);
 
-        Ok((self_closing, span))
+        Ok((self_closing_slash, span))
     }
 
     /// Parse a closing tag and return its [`NodeName`] and [Span]
@@ -305,32 +1086,99 @@ impl Parser {
                 break;
             }
 
-            nodes.push(self.attribute(input)?);
+            let attribute = self.attribute(input)?;
+            if let Some(max) = self.config.max_attributes_per_element {
+                if nodes.len() == max {
+                    return Err(Error::new(
+                        attribute.span(),
+                        format!("elements can't have more than {} attributes", max),
+                    ));
+                }
+            }
+
+            nodes.push(attribute);
+            self.count_nodes(1);
         }
 
         Ok(nodes)
     }
 
     /// Parse the stream as [`Node::Attribute`].
-    fn attribute(&self, input: ParseStream) -> Result<Node> {
-        let fork = &input.fork();
+    pub(crate) fn attribute(&self, input: ParseStream) -> Result<Node> {
+        let fork = &self.fork(input);
         if fork.peek(Brace) {
-            let value = self.block_expr(fork)?.into();
+            let value = self.block_expr(fork)?;
             input.advance_to(fork);
 
-            Ok(Node::Block(NodeBlock { value }))
+            if self.config.shorthand_attributes {
+                if let Some(ident) = shorthand_ident(&value) {
+                    let span = value.span();
+                    let path = ExprPath {
+                        attrs: vec![],
+                        qself: None,
+                        path: Path::from(ident.clone()),
+                    };
+                    let key = NodeName::Path(path.clone());
+                    self.check_allowed_attribute(&key)?;
+                    let value = NodeValueExpr::new(Expr::Path(path));
+
+                    return Ok(Node::Attribute(NodeAttribute::new(key, Some(value), span)));
+                }
+            }
+
+            Ok(Node::Block(NodeBlock {
+                value: value.into(),
+            }))
         } else {
-            let key = self.node_name(fork)?;
-            let eq = fork.parse::<Option<Token![=]>>()?;
-            let value = if eq.is_some() {
+            let negation = if self.config.allow_attribute_negation {
+                fork.parse::<Option<Token![!]>>()?
+            } else {
+                None
+            };
+            let key = self.attribute_key(fork)?;
+            self.check_allowed_attribute(&key)?;
+            let eq_span =
+                if self.config.colon_attribute_values && fork.peek(Colon) && !fork.peek(Colon2) {
+                    Some(fork.parse::<Colon>()?.span)
+                } else {
+                    fork.parse::<Option<Token![=]>>()?.map(|eq| eq.span)
+                };
+            let mut value_list = false;
+            let mut template = None;
+            let value = if eq_span.is_some() {
                 if fork.is_empty() {
                     return Err(Error::new(key.span(), "missing attribute value"));
                 }
 
-                if fork.peek(Brace) {
-                    Some(NodeValueExpr::new(self.block_expr(fork)?))
+                let value = self.attribute_value(fork)?;
+                if self.config.comma_separated_attribute_values && fork.peek(Token![,]) {
+                    let paren_token = Paren { span: value.span() };
+                    let mut elems = Punctuated::new();
+                    elems.push_value(value);
+                    while fork.peek(Token![,]) {
+                        elems.push_punct(fork.parse()?);
+                        elems.push_value(self.attribute_value(fork)?);
+                    }
+                    value_list = true;
+                    Some(NodeValueExpr::new(
+                        ExprTuple {
+                            attrs: vec![],
+                            paren_token,
+                            elems,
+                        }
+                        .into(),
+                    ))
                 } else {
-                    Some(NodeValueExpr::new(fork.parse()?))
+                    if let (
+                        true,
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ) = (self.config.interpolate_string_attributes, &value)
+                    {
+                        template = Some(template_parts(lit)?);
+                    }
+                    Some(NodeValueExpr::new(value))
                 }
             } else {
                 None
@@ -341,20 +1189,85 @@ impl Parser {
             } else {
                 key.span()
             };
-            Ok(Node::Attribute(NodeAttribute { key, value, span }))
+            let span = negation
+                .and_then(|negation| negation.span.join(span))
+                .unwrap_or(span);
+            let directive = match &key {
+                NodeName::Punctuated(name) => name
+                    .first()
+                    .map(|prefix| {
+                        self.config
+                            .attribute_directives
+                            .contains(prefix.to_string().as_str())
+                    })
+                    .unwrap_or(false),
+                _ => false,
+            };
+
+            Ok(Node::Attribute(NodeAttribute {
+                key,
+                value,
+                span,
+                eq_span,
+                value_list,
+                template,
+                directive,
+                negated: negation.is_some(),
+            }))
         }
     }
 
+    /// Check the key against [`ParserConfig::allowed_attributes`].
+    ///
+    /// [`ParserConfig::allowed_attributes`]: struct.ParserConfig.html#method.allowed_attributes
+    fn check_allowed_attribute(&self, key: &NodeName) -> Result<()> {
+        if let Some(allowed_attributes) = &self.config.allowed_attributes {
+            if !allowed_attributes.contains(key.to_string().as_str()) {
+                return Err(Error::new(
+                    key.span(),
+                    format!("attribute `{}` is not allowed", key),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse the stream as attribute value, either a block or an expression.
+    fn attribute_value(&self, input: ParseStream) -> Result<Expr> {
+        if input.peek(Brace) {
+            return self.block_expr(input);
+        }
+
+        let value = input.parse()?;
+        if self.config.require_braced_attribute_values && !matches!(value, Expr::Lit(_)) {
+            return Err(Error::new(
+                value.span(),
+                "attribute values that aren't literals need to be wrapped in braces",
+            ));
+        }
+
+        Ok(value)
+    }
+
     /// Parse the stream as [`Node::Doctype`].
     fn doctype(&self, input: ParseStream) -> Result<Node> {
         let span_start = input.span();
         input.parse::<Token![<]>()?;
         input.parse::<Token![!]>()?;
-        let ident = input.parse::<Ident>()?;
-        if ident.to_string().to_lowercase() != "doctype" {
-            return Err(input.error("expected Doctype"));
+        let keyword = input.parse::<Ident>()?;
+        if keyword.to_string().to_lowercase() != "doctype" {
+            return Err(self.error(input, "expected Doctype"));
         }
         let doctype = input.parse::<Ident>()?;
+        let internal_subset = if input.peek(Bracket) {
+            match input.parse::<TokenTree>()? {
+                TokenTree::Group(group) => Some(group),
+                _ => unreachable!("peeked a bracket group"),
+            }
+        } else {
+            None
+        };
         let span_end = input.span();
         let doctype_span = doctype.span();
         input.parse::<Token![>]>()?;
@@ -374,7 +1287,12 @@ impl Parser {
         );
 
         let span = span_start.join(span_end).unwrap_or(doctype_span);
-        Ok(Node::Doctype(NodeDoctype { value, span }))
+        Ok(Node::Doctype(NodeDoctype {
+            value,
+            span,
+            keyword,
+            internal_subset,
+        }))
     }
 
     /// Parse the stream as [`Node::Comment`].
@@ -397,17 +1315,24 @@ impl Parser {
     /// Parse the stream as [`Node::Fragement`].
     fn fragment(&self, input: ParseStream) -> Result<Node> {
         let mut span = self.fragment_open(input)?;
+        self.emit(|| Event::OpenFragment(span));
 
         let mut children = vec![];
         loop {
             if input.is_empty() {
-                return Err(input.error("unexpected end of input"));
+                if self.config.allow_unclosed_fragment {
+                    self.warn(span, "fragment has no corresponding close tag `</>`");
+                    self.emit(|| Event::CloseFragment(span));
+                    break;
+                }
+                return Err(self.error(input, "unexpected end of input"));
             }
 
-            let fork = input.fork();
+            let fork = self.fork(input);
             if let Ok(closing_span) = self.fragment_close(&fork) {
                 input.advance_to(&fork);
                 span = span.join(closing_span).unwrap_or(span);
+                self.emit(|| Event::CloseFragment(closing_span));
                 break;
             }
 
@@ -456,29 +1381,55 @@ impl Parser {
                     })
                 })
         } else if input.peek2(Colon) || input.peek2(Dash) {
-            self.node_name_punctuated_ident_with_alternate::<Punct, fn(_) -> Colon, fn(_) -> Dash, Ident>(
-                input, Colon, Dash,
-            )
-            .map(NodeName::Punctuated)
+            self.node_name_punctuated(input, true)
+                .map(NodeName::Punctuated)
         } else if input.peek(Brace) {
-            let fork = &input.fork();
+            let fork = &self.fork(input);
             let value = self.block_expr(fork)?;
+            if let (Some(validate), Expr::Block(expr)) =
+                (&self.config.validate_block_tag_name, &value)
+            {
+                validate(&expr.block).map_err(|message| Error::new(value.span(), message))?;
+            }
             input.advance_to(fork);
             Ok(NodeName::Block(value))
         } else if input.peek(Ident::peek_any) {
-            let mut segments = Punctuated::new();
-            let ident = Ident::parse_any(input)?;
-            segments.push_value(PathSegment::from(ident));
-            Ok(NodeName::Path(ExprPath {
-                attrs: vec![],
-                qself: None,
-                path: Path {
-                    leading_colon: None,
-                    segments,
-                },
-            }))
+            self.node_name_ident(input)
+        } else {
+            Err(self.error(input, "invalid tag name or attribute key"))
+        }
+    }
+
+    /// Parse the stream as a single identifier [`NodeName::Path`].
+    fn node_name_ident(&self, input: ParseStream) -> Result<NodeName> {
+        let mut segments = Punctuated::new();
+        let ident = Ident::parse_any(input)?;
+        segments.push_value(PathSegment::from(ident));
+        Ok(NodeName::Path(ExprPath {
+            attrs: vec![],
+            qself: None,
+            path: Path {
+                leading_colon: None,
+                segments,
+            },
+        }))
+    }
+
+    /// Parse the stream as attribute key. With
+    /// [`ParserConfig::colon_attribute_values`] a single colon separates the
+    /// value instead of being part of the key.
+    ///
+    /// [`ParserConfig::colon_attribute_values`]: struct.ParserConfig.html#method.colon_attribute_values
+    fn attribute_key(&self, input: ParseStream) -> Result<NodeName> {
+        if !self.config.colon_attribute_values || input.peek2(Colon2) {
+            self.node_name(input)
+        } else if input.peek2(Dash) {
+            self.node_name_punctuated(input, false)
+                .map(NodeName::Punctuated)
+        } else if input.peek2(Colon) {
+            self.node_name_ident(input)
         } else {
-            Err(input.error("invalid tag name or attribute key"))
+            self.node_name(input)
         }
     }
 
@@ -494,7 +1445,7 @@ impl Parser {
         input: ParseStream,
         punct: F,
     ) -> Result<Punctuated<X, T>> {
-        let fork = &input.fork();
+        let fork = &self.fork(input);
         let mut segments = Punctuated::<X, T>::new();
 
         while !fork.is_empty() && fork.peek(Ident::peek_any) {
@@ -512,26 +1463,33 @@ impl Parser {
             input.advance_to(fork);
             Ok(segments)
         } else {
-            Err(fork.error("expected punctuated node name"))
+            Err(self.error(fork, "expected punctuated node name"))
         }
     }
 
-    /// Parse the stream as punctuated idents, with two possible punctuations
-    /// available
-    fn node_name_punctuated_ident_with_alternate<T: Parse, F: Peek, G: Peek, X: From<Ident>>(
+    /// Parse the stream as idents punctuated by dashes and, if `colons` is
+    /// set, colons. Segments after a dash can also be integer literals, e.g.
+    /// `x-5` or `grid-2x`.
+    fn node_name_punctuated(
         &self,
         input: ParseStream,
-        punct: F,
-        alternate_punct: G,
-    ) -> Result<Punctuated<X, T>> {
-        let fork = &input.fork();
-        let mut segments = Punctuated::<X, T>::new();
-
-        while !fork.is_empty() && fork.peek(Ident::peek_any) {
-            let ident = Ident::parse_any(fork)?;
-            segments.push_value(ident.clone().into());
+        colons: bool,
+    ) -> Result<Punctuated<NodeNameFragment, Punct>> {
+        let fork = &self.fork(input);
+        let mut segments = Punctuated::<NodeNameFragment, Punct>::new();
+        let mut after_dash = false;
+
+        while !fork.is_empty() {
+            if fork.peek(Ident::peek_any) {
+                segments.push_value(Ident::parse_any(fork)?.into());
+            } else if after_dash && fork.peek(LitInt) {
+                segments.push_value(fork.parse::<LitInt>()?.into());
+            } else {
+                break;
+            }
 
-            if fork.peek(punct) || fork.peek(alternate_punct) {
+            after_dash = fork.peek(Dash);
+            if (colons && fork.peek(Colon)) || after_dash {
                 segments.push_punct(fork.parse()?);
             } else {
                 break;
@@ -542,7 +1500,114 @@ impl Parser {
             input.advance_to(fork);
             Ok(segments)
         } else {
-            Err(fork.error("expected punctuated node name"))
+            Err(self.error(fork, "expected punctuated node name"))
         }
     }
 }
+
+/// Split the string literal into literal text and `{ident}` placeholders.
+fn template_parts(lit: &LitStr) -> Result<Vec<TemplatePart>> {
+    let span = lit.span();
+    let value = lit.value();
+    let mut parts = vec![];
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(char) => name.push(char),
+                        None => return Err(Error::new(span, "placeholder is not closed")),
+                    }
+                }
+                let mut ident = syn::parse_str::<Ident>(&name)
+                    .map_err(|_| Error::new(span, format!("invalid placeholder `{{{}}}`", name)))?;
+                ident.set_span(span);
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(TemplatePart::Expr(ident));
+            }
+            '}' => return Err(Error::new(span, "unmatched `}` in placeholder string")),
+            _ => literal.push(char),
+        }
+    }
+
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+
+    Ok(parts)
+}
+
+/// Get the identifier of a block that only contains an identifier, e.g.
+/// `{value}`.
+fn shorthand_ident(block: &Expr) -> Option<&Ident> {
+    match block {
+        Expr::Block(ExprBlock { block, .. }) => match block.stmts.as_slice() {
+            [Stmt::Expr(Expr::Path(ExprPath {
+                qself: None, path, ..
+            }))] => path.get_ident(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the byte offset of the end of an interpolation that starts at the
+/// beginning of the given string, skipping nested braces.
+fn interpolation_end(interpolation: &str, syntax: InterpolationSyntax) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, char) in interpolation.char_indices() {
+        if depth == 0 && interpolation[index..].starts_with(syntax.close()) {
+            return Some(index);
+        }
+
+        match char {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Set the span of all tokens, recursing into groups.
+pub(crate) fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+/// Count the tokens in the stream, including tokens inside groups.
+fn count_tokens(tokens: TokenStream) -> usize {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
@@ -0,0 +1,64 @@
+//! Tiny selector engine to find elements by tag name and class.
+
+use std::convert::TryFrom;
+
+use crate::{Node, NodeElement};
+
+/// Find all elements with the given tag name and, optionally, the given class,
+/// similar to the CSS selector `tag.class`. Matches are returned in document
+/// order.
+///
+/// Only static `class` attribute values, i.e. string literals, are matched.
+/// Dynamic class values like `class={classes}` can't be known at parse time
+/// and never match.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{parse2, select};
+///
+/// let nodes = parse2(quote! {
+///     <div class="active">"a"</div>
+///     <div>"b"</div>
+/// })
+/// .unwrap();
+///
+/// assert_eq!(select::find(&nodes, "div", None).len(), 2);
+/// assert_eq!(select::find(&nodes, "div", Some("active")).len(), 1);
+/// ```
+pub fn find<'a>(nodes: &'a [Node], tag: &str, class: Option<&str>) -> Vec<&'a NodeElement> {
+    let mut elements = vec![];
+    find_into(nodes, tag, class, &mut elements);
+    elements
+}
+
+fn find_into<'a>(
+    nodes: &'a [Node],
+    tag: &str,
+    class: Option<&str>,
+    elements: &mut Vec<&'a NodeElement>,
+) {
+    for node in nodes {
+        if let Node::Element(element) = node {
+            if element.name.to_string() == tag
+                && class.iter().all(|class| has_class(element, class))
+            {
+                elements.push(element);
+            }
+        }
+
+        if let Some(children) = node.children() {
+            find_into(children, tag, class, elements);
+        }
+    }
+}
+
+fn has_class(element: &NodeElement, class: &str) -> bool {
+    element.attributes.iter().any(|attribute| match attribute {
+        Node::Attribute(attribute) if attribute.key.to_string() == "class" => attribute
+            .value
+            .as_ref()
+            .and_then(|value| String::try_from(value).ok())
+            .is_some_and(|value| value.split_whitespace().any(|name| name == class)),
+        _ => false,
+    })
+}
@@ -0,0 +1,111 @@
+//! Pair `<slot>` definitions with the elements assigned to them, e.g. for
+//! web component style slotting.
+
+use std::convert::TryFrom;
+
+use crate::{Node, NodeElement};
+
+/// A `<slot>` definition and the elements assigned to it.
+#[derive(Debug)]
+pub struct Slot<'a> {
+    /// Value of the `name` attribute, `None` for the default slot.
+    pub name: Option<String>,
+    /// The `<slot>` element.
+    pub definition: &'a NodeElement,
+    /// Elements with a `slot` attribute of the same name, in document order.
+    pub assigned: Vec<&'a NodeElement>,
+}
+
+/// Slots found by [`collect`].
+#[derive(Debug, Default)]
+pub struct SlotInfo<'a> {
+    /// The `<slot>` definitions in document order.
+    pub slots: Vec<Slot<'a>>,
+    /// Elements with a `slot` attribute that doesn't name any definition.
+    pub unassigned: Vec<&'a NodeElement>,
+}
+
+/// Find the `<slot>` definitions and the elements with a `slot` attribute,
+/// pairing them by name. The first definition of a name gets the assigned
+/// elements.
+///
+/// Only static attribute values, i.e. string literals, are considered. A
+/// `<slot>` with a dynamic `name` is treated as the default slot and elements
+/// with a dynamic `slot` value are never assigned.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{parse2, slots};
+///
+/// let nodes = parse2(quote! {
+///     <slot name="header" />
+///     <h1 slot="header">"Title"</h1>
+/// })
+/// .unwrap();
+/// let info = slots::collect(&nodes);
+///
+/// assert_eq!(info.slots[0].assigned.len(), 1);
+/// ```
+pub fn collect(nodes: &[Node]) -> SlotInfo<'_> {
+    let mut definitions = vec![];
+    let mut assignments = vec![];
+    collect_into(nodes, &mut definitions, &mut assignments);
+
+    let mut info = SlotInfo {
+        slots: definitions
+            .into_iter()
+            .map(|definition| Slot {
+                name: static_attribute(definition, "name"),
+                definition,
+                assigned: vec![],
+            })
+            .collect(),
+        unassigned: vec![],
+    };
+    for (name, element) in assignments {
+        match info
+            .slots
+            .iter_mut()
+            .find(|slot| slot.name.as_deref() == Some(name.as_str()))
+        {
+            Some(slot) => slot.assigned.push(element),
+            None => info.unassigned.push(element),
+        }
+    }
+
+    info
+}
+
+fn collect_into<'a>(
+    nodes: &'a [Node],
+    definitions: &mut Vec<&'a NodeElement>,
+    assignments: &mut Vec<(String, &'a NodeElement)>,
+) {
+    for node in nodes {
+        if let Node::Element(element) = node {
+            if element.name.to_string() == "slot" {
+                definitions.push(element);
+            }
+            if let Some(name) = static_attribute(element, "slot") {
+                assignments.push((name, element));
+            }
+        }
+
+        if let Some(children) = node.children() {
+            collect_into(children, definitions, assignments);
+        }
+    }
+}
+
+fn static_attribute(element: &NodeElement, key: &str) -> Option<String> {
+    element
+        .attributes
+        .iter()
+        .find_map(|attribute| match attribute {
+            Node::Attribute(attribute) if attribute.key.to_string() == key => attribute
+                .value
+                .as_ref()
+                .and_then(|value| String::try_from(value).ok()),
+            _ => None,
+        })
+}
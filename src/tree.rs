@@ -0,0 +1,432 @@
+//! Helpers working on a tree of nodes.
+
+use std::collections::HashSet;
+
+use proc_macro2::Span;
+use syn::{spanned::Spanned, Expr, ExprBlock, ExprLit, ExprRange, Lit, Stmt};
+
+use crate::{Node, NodeComment, NodeName};
+
+/// Get the run of comments before the first non-comment node, e.g. a license
+/// header in front of the doctype.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{leading_comments, parse2};
+///
+/// let nodes = parse2(quote! {
+///     <!-- "license" -->
+///     <!DOCTYPE html>
+///     <!-- "not leading" -->
+/// })
+/// .unwrap();
+///
+/// assert_eq!(leading_comments(&nodes).len(), 1);
+/// ```
+pub fn leading_comments(nodes: &[Node]) -> &[Node] {
+    let end = nodes
+        .iter()
+        .position(|node| !matches!(node, Node::Comment(_)))
+        .unwrap_or(nodes.len());
+
+    &nodes[..end]
+}
+
+/// Node with the comments immediately preceding it, see
+/// [`attach_leading_comments`].
+#[derive(Debug)]
+pub struct AnnotatedNode {
+    /// Comments in front of the node, in source order.
+    pub comments: Vec<NodeComment>,
+    /// The node.
+    pub node: Node,
+}
+
+/// Attach the run of comments immediately preceding an element to it, like
+/// doc comments, e.g. to generate docs from commented templates. Comments
+/// that aren't followed by an element stay nodes of their own. Only the given
+/// siblings are reshaped, children are left as is.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{attach_leading_comments, parse2};
+///
+/// let nodes = parse2(quote! {
+///     <!-- "The header" -->
+///     <header />
+/// })
+/// .unwrap();
+/// let annotated = attach_leading_comments(nodes);
+///
+/// assert_eq!(annotated.len(), 1);
+/// assert_eq!(annotated[0].comments.len(), 1);
+/// ```
+pub fn attach_leading_comments(nodes: Vec<Node>) -> Vec<AnnotatedNode> {
+    let mut annotated = vec![];
+    let mut comments = vec![];
+    for node in nodes {
+        match node {
+            Node::Comment(comment) => comments.push(comment),
+            Node::Element(_) => annotated.push(AnnotatedNode {
+                comments: std::mem::take(&mut comments),
+                node,
+            }),
+            _ => {
+                annotated.extend(comments.drain(..).map(unannotated_comment));
+                annotated.push(AnnotatedNode {
+                    comments: vec![],
+                    node,
+                });
+            }
+        }
+    }
+    annotated.extend(comments.into_iter().map(unannotated_comment));
+
+    annotated
+}
+
+fn unannotated_comment(comment: NodeComment) -> AnnotatedNode {
+    AnnotatedNode {
+        comments: vec![],
+        node: Node::Comment(comment),
+    }
+}
+
+/// Call the given closure with every node and its previous and next sibling,
+/// in document order, recursing into children with their own siblings, e.g.
+/// for whitespace handling between inline elements. Attributes aren't
+/// visited.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{parse2, with_siblings};
+///
+/// let nodes = parse2(quote! { <a /><b /><c /> }).unwrap();
+/// let mut neighbours = vec![];
+///
+/// with_siblings(&nodes, |previous, _, next| {
+///     neighbours.push((previous.is_some(), next.is_some()));
+/// });
+///
+/// assert_eq!(neighbours, [(false, true), (true, true), (true, false)]);
+/// ```
+pub fn with_siblings<F>(nodes: &[Node], mut f: F)
+where
+    F: FnMut(Option<&Node>, &Node, Option<&Node>),
+{
+    with_siblings_with(nodes, &mut f);
+}
+
+fn with_siblings_with<F>(nodes: &[Node], f: &mut F)
+where
+    F: FnMut(Option<&Node>, &Node, Option<&Node>),
+{
+    for (index, node) in nodes.iter().enumerate() {
+        let previous = index.checked_sub(1).and_then(|index| nodes.get(index));
+        f(previous, node, nodes.get(index + 1));
+
+        match node {
+            Node::Element(element) => with_siblings_with(&element.children, f),
+            Node::Fragment(fragment) => with_siblings_with(&fragment.children, f),
+            _ => (),
+        }
+    }
+}
+
+/// Call the given closure with the key and a mutable value of every attribute
+/// that has a value, recursing into children. Useful for bulk rewrites like
+/// prefixing all URLs.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn::parse_quote;
+/// use syn_rsx::{map_attribute_values, parse2};
+///
+/// let mut nodes = parse2(quote! { <a href="/home" /> }).unwrap();
+///
+/// map_attribute_values(&mut nodes, |key, value| {
+///     if key.to_string() == "href" {
+///         *value = parse_quote! { url(#value) };
+///     }
+/// });
+/// ```
+pub fn map_attribute_values<F>(nodes: &mut [Node], mut f: F)
+where
+    F: FnMut(&NodeName, &mut Expr),
+{
+    map_attribute_values_with(nodes, &mut f);
+}
+
+fn map_attribute_values_with<F>(nodes: &mut [Node], f: &mut F)
+where
+    F: FnMut(&NodeName, &mut Expr),
+{
+    for node in nodes {
+        match node {
+            Node::Attribute(attribute) => {
+                if let Some(value) = attribute.value.as_mut() {
+                    f(&attribute.key, value.as_mut());
+                }
+            }
+            Node::Element(element) => {
+                map_attribute_values_with(&mut element.attributes, f);
+                map_attribute_values_with(&mut element.children, f);
+            }
+            Node::Fragment(fragment) => map_attribute_values_with(&mut fragment.children, f),
+            _ => (),
+        }
+    }
+}
+
+/// Get the spans of all elements named `self_name`, recursing into children,
+/// e.g. to reject a component that uses itself in its own markup.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn::parse_quote;
+/// use syn_rsx::{find_recursive_components, parse2, NodeName};
+///
+/// let nodes = parse2(quote! { <div><Foo /></div> }).unwrap();
+/// let name = NodeName::Path(parse_quote! { Foo });
+///
+/// assert_eq!(find_recursive_components(&nodes, &name).len(), 1);
+/// ```
+pub fn find_recursive_components(nodes: &[Node], self_name: &NodeName) -> Vec<Span> {
+    let mut spans = vec![];
+    find_elements_named(nodes, self_name, &mut spans);
+    spans
+}
+
+fn find_elements_named(nodes: &[Node], name: &NodeName, spans: &mut Vec<Span>) {
+    for node in nodes {
+        match node {
+            Node::Element(element) => {
+                if &element.name == name {
+                    spans.push(element.span);
+                }
+                find_elements_named(&element.children, name, spans);
+            }
+            Node::Fragment(fragment) => find_elements_named(&fragment.children, name, spans),
+            _ => (),
+        }
+    }
+}
+
+/// Get the distinct names of all elements, recursing into children, e.g. to
+/// diff them against a registry of known components.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{collect_element_names, parse2};
+///
+/// let nodes = parse2(quote! { <div><Card /><Card /></div> }).unwrap();
+/// let names = collect_element_names(&nodes);
+///
+/// assert_eq!(names.len(), 2);
+/// assert!(names.contains("Card"));
+/// ```
+pub fn collect_element_names(nodes: &[Node]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    collect_names(nodes, &mut names);
+    names
+}
+
+fn collect_names(nodes: &[Node], names: &mut HashSet<String>) {
+    for node in nodes {
+        match node {
+            Node::Element(element) => {
+                names.insert(element.name.to_string());
+                collect_names(&element.children, names);
+            }
+            Node::Fragment(fragment) => collect_names(&fragment.children, names),
+            _ => (),
+        }
+    }
+}
+
+/// Get the value and span of every string literal text node and of string
+/// literal values of the given `attributes`, in source order, recursing into
+/// children. Meant to feed a translation extraction pipeline, `attributes`
+/// would usually be user-visible ones like `alt`, `title` or `placeholder`.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{extract_strings, parse2};
+///
+/// let nodes = parse2(quote! { <img alt="Logo" src="/logo.png" /> }).unwrap();
+/// let strings = extract_strings(&nodes, &["alt"]);
+///
+/// assert_eq!(strings[0].0, "Logo");
+/// ```
+pub fn extract_strings(nodes: &[Node], attributes: &[&str]) -> Vec<(String, Span)> {
+    let mut strings = vec![];
+    collect_strings(nodes, attributes, &mut strings);
+    strings
+}
+
+fn collect_strings(nodes: &[Node], attributes: &[&str], strings: &mut Vec<(String, Span)>) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                if let Some(string) = lit_str(&text.value) {
+                    strings.push(string);
+                }
+            }
+            Node::Attribute(attribute) => {
+                let key = attribute.key.to_string();
+                if attributes.contains(&key.as_str()) {
+                    if let Some(string) = attribute.value.as_ref().and_then(|v| lit_str(v)) {
+                        strings.push(string);
+                    }
+                }
+            }
+            Node::Element(element) => {
+                collect_strings(&element.attributes, attributes, strings);
+                collect_strings(&element.children, attributes, strings);
+            }
+            Node::Fragment(fragment) => collect_strings(&fragment.children, attributes, strings),
+            _ => (),
+        }
+    }
+}
+
+fn lit_str(expr: &Expr) -> Option<(String, Span)> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => Some((lit_str.value(), lit_str.span())),
+        _ => None,
+    }
+}
+
+/// Place where Rust code is embedded in the tree, see [`dynamic_parts`].
+#[derive(Debug)]
+pub enum DynamicPart<'a> {
+    /// Block child, e.g. `{value}` in `<div>{value}</div>`.
+    ChildBlock { span: Span, expr: &'a Expr },
+    /// Attribute value that isn't a literal, e.g. `{value}` in
+    /// `<div key={value} />`.
+    AttributeValue {
+        span: Span,
+        key: &'a NodeName,
+        expr: &'a Expr,
+    },
+    /// Block attribute with a spread like `{..props}`.
+    SpreadAttribute { span: Span, expr: &'a Expr },
+    /// Block attribute, e.g. `{props}` in `<div {props} />`.
+    BlockAttribute { span: Span, expr: &'a Expr },
+    /// Block tag name, e.g. `{tag}` in `<{tag} />`.
+    BlockTagName { span: Span, expr: &'a Expr },
+}
+
+impl<'a> DynamicPart<'a> {
+    /// Span of the embedded code.
+    pub fn span(&self) -> Span {
+        match self {
+            DynamicPart::ChildBlock { span, .. }
+            | DynamicPart::AttributeValue { span, .. }
+            | DynamicPart::SpreadAttribute { span, .. }
+            | DynamicPart::BlockAttribute { span, .. }
+            | DynamicPart::BlockTagName { span, .. } => *span,
+        }
+    }
+
+    /// The embedded code.
+    pub fn expr(&self) -> &'a Expr {
+        match self {
+            DynamicPart::ChildBlock { expr, .. }
+            | DynamicPart::AttributeValue { expr, .. }
+            | DynamicPart::SpreadAttribute { expr, .. }
+            | DynamicPart::BlockAttribute { expr, .. }
+            | DynamicPart::BlockTagName { expr, .. } => expr,
+        }
+    }
+}
+
+/// Get every place where Rust code is embedded, in source order, recursing
+/// into children. Useful for reactivity analysis.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{dynamic_parts, parse2, DynamicPart};
+///
+/// let nodes = parse2(quote! { <div class="static" key={value}>{child}</div> }).unwrap();
+/// let parts = dynamic_parts(&nodes);
+///
+/// assert!(matches!(parts[0], DynamicPart::AttributeValue { .. }));
+/// assert!(matches!(parts[1], DynamicPart::ChildBlock { .. }));
+/// ```
+pub fn dynamic_parts(nodes: &[Node]) -> Vec<DynamicPart<'_>> {
+    let mut parts = vec![];
+    collect_dynamic_parts(nodes, &mut parts);
+    parts
+}
+
+fn collect_dynamic_parts<'a>(nodes: &'a [Node], parts: &mut Vec<DynamicPart<'a>>) {
+    for node in nodes {
+        match node {
+            Node::Element(element) => {
+                if let NodeName::Block(expr) = &element.name {
+                    parts.push(DynamicPart::BlockTagName {
+                        span: expr.span(),
+                        expr,
+                    });
+                }
+                for attribute in &element.attributes {
+                    match attribute {
+                        Node::Block(block) if is_spread(&block.value) => {
+                            parts.push(DynamicPart::SpreadAttribute {
+                                span: block.value.span(),
+                                expr: &block.value,
+                            })
+                        }
+                        Node::Block(block) => parts.push(DynamicPart::BlockAttribute {
+                            span: block.value.span(),
+                            expr: &block.value,
+                        }),
+                        _ => collect_dynamic_parts(std::slice::from_ref(attribute), parts),
+                    }
+                }
+                collect_dynamic_parts(&element.children, parts);
+            }
+            Node::Attribute(attribute) => match attribute.value.as_ref() {
+                Some(value) if !matches!(value.as_ref(), Expr::Lit(_)) => {
+                    parts.push(DynamicPart::AttributeValue {
+                        span: value.span(),
+                        key: &attribute.key,
+                        expr: value,
+                    })
+                }
+                _ => (),
+            },
+            Node::Block(block) => parts.push(DynamicPart::ChildBlock {
+                span: block.value.span(),
+                expr: &block.value,
+            }),
+            Node::Fragment(fragment) => collect_dynamic_parts(&fragment.children, parts),
+            _ => (),
+        }
+    }
+}
+
+/// Whether the block only contains a spread like `{..props}`.
+fn is_spread(expr: &Expr) -> bool {
+    spread_expr(expr).is_some()
+}
+
+/// Get the spread expression of a block that only contains a spread, e.g.
+/// `props` in `{..props}`.
+pub(crate) fn spread_expr(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Block(ExprBlock { block, .. }) => match block.stmts.as_slice() {
+            [Stmt::Expr(Expr::Range(ExprRange {
+                from: None,
+                to: Some(to),
+                ..
+            }))] => Some(to),
+            _ => None,
+        },
+        _ => None,
+    }
+}
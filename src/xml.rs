@@ -0,0 +1,144 @@
+//! XML namespace helpers.
+
+use std::{collections::HashMap, marker::PhantomData};
+
+use syn::{Expr, ExprLit, Lit};
+
+use crate::{Node, NodeAttribute, NodeElement, NodeName};
+
+/// Namespace URI of the `xmlns` declarations themselves.
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Resolved namespaces of the elements and attributes in a tree, see
+/// [`resolve_namespaces`].
+///
+/// Nodes are looked up by identity, so only the nodes of the resolved tree
+/// have namespaces, not equal nodes of other trees.
+#[derive(Debug, Default)]
+pub struct NamespaceMap<'a> {
+    elements: HashMap<*const NodeElement, Option<String>>,
+    attributes: HashMap<*const NodeAttribute, Option<String>>,
+    /// The keys point into the resolved tree.
+    nodes: PhantomData<&'a [Node]>,
+}
+
+impl<'a> NamespaceMap<'a> {
+    /// Get the namespace URI of the given element, if it has one.
+    pub fn element(&self, element: &NodeElement) -> Option<&str> {
+        self.elements
+            .get(&(element as *const NodeElement))
+            .and_then(Option::as_deref)
+    }
+
+    /// Get the namespace URI of the given attribute, if it has one.
+    pub fn attribute(&self, attribute: &NodeAttribute) -> Option<&str> {
+        self.attributes
+            .get(&(attribute as *const NodeAttribute))
+            .and_then(Option::as_deref)
+    }
+}
+
+/// Walk the tree and resolve the namespace of every element and attribute
+/// based on the `xmlns` and `xmlns:prefix` declarations in scope.
+///
+/// Unprefixed elements get the default namespace declared with `xmlns`,
+/// unprefixed attributes don't have a namespace. Only attribute values that
+/// are string literals are considered as declarations.
+///
+/// ```rust
+/// use quote::quote;
+/// use syn_rsx::{parse2, xml::resolve_namespaces, Node};
+///
+/// let nodes = parse2(quote! {
+///     <svg xmlns="http://www.w3.org/2000/svg"><rect /></svg>
+/// })
+/// .unwrap();
+/// let namespaces = resolve_namespaces(&nodes);
+///
+/// let Node::Element(svg) = &nodes[0] else {
+///     panic!()
+/// };
+/// let Node::Element(rect) = &svg.children[0] else {
+///     panic!()
+/// };
+/// assert_eq!(namespaces.element(rect), Some("http://www.w3.org/2000/svg"));
+/// ```
+pub fn resolve_namespaces(nodes: &[Node]) -> NamespaceMap<'_> {
+    let mut map = NamespaceMap::default();
+    resolve_nodes(nodes, &HashMap::new(), &mut map);
+    map
+}
+
+fn resolve_nodes<'a>(
+    nodes: &'a [Node],
+    scope: &HashMap<Option<String>, String>,
+    map: &mut NamespaceMap<'a>,
+) {
+    for node in nodes {
+        match node {
+            Node::Element(element) => resolve_element(element, scope, map),
+            Node::Fragment(fragment) => resolve_nodes(&fragment.children, scope, map),
+            _ => (),
+        }
+    }
+}
+
+fn resolve_element<'a>(
+    element: &'a NodeElement,
+    scope: &HashMap<Option<String>, String>,
+    map: &mut NamespaceMap<'a>,
+) {
+    let mut scope = scope.clone();
+    for attribute in &element.attributes {
+        let Node::Attribute(attribute) = attribute else {
+            continue;
+        };
+        let Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(uri), ..
+        })) = attribute.value.as_deref()
+        else {
+            continue;
+        };
+        let uri = uri.value();
+
+        match split_prefix(&attribute.key) {
+            (None, local) if local == "xmlns" => {
+                scope.insert(None, uri);
+            }
+            (Some(prefix), local) if prefix == "xmlns" => {
+                scope.insert(Some(local), uri);
+            }
+            _ => (),
+        }
+    }
+
+    let (prefix, _) = split_prefix(&element.name);
+    map.elements.insert(element, scope.get(&prefix).cloned());
+
+    for attribute in &element.attributes {
+        let Node::Attribute(attribute) = attribute else {
+            continue;
+        };
+        let namespace = match split_prefix(&attribute.key) {
+            (Some(prefix), _) if prefix == "xmlns" => Some(XMLNS_NAMESPACE.to_owned()),
+            (None, local) if local == "xmlns" => Some(XMLNS_NAMESPACE.to_owned()),
+            (Some(prefix), _) => scope.get(&Some(prefix)).cloned(),
+            (None, _) => None,
+        };
+        map.attributes.insert(attribute, namespace);
+    }
+
+    resolve_nodes(&element.children, &scope, map);
+}
+
+/// Split a colon separated name into its prefix and local name.
+fn split_prefix(name: &NodeName) -> (Option<String>, String) {
+    let name_string = name.to_string();
+    match name {
+        NodeName::Punctuated(_) => match name_string.split_once(':') {
+            Some((prefix, local)) => (Some(prefix.to_owned()), local.to_owned()),
+            None => (None, name_string),
+        },
+        _ => (None, name_string),
+    }
+}
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{collections::HashSet, convert::TryFrom};
 
 use eyre::Result;
 use quote::quote;
@@ -52,6 +52,44 @@ fn test_single_element_with_text() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_raw_string_attribute_value() -> Result<()> {
+    let tokens = quote! {
+        <div data-json=r#"{"a":1}"# data-plain="{}" />
+    };
+    let nodes = parse2(tokens)?;
+
+    let raw = get_element_attribute(&nodes, 0, 0);
+    let plain = get_element_attribute(&nodes, 0, 1);
+
+    assert!(raw.value_is_raw_string());
+    assert_eq!(
+        String::try_from(raw.value.as_ref().expect("value"))?,
+        r#"{"a":1}"#
+    );
+    assert!(!plain.value_is_raw_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_text_raw_literal() -> Result<()> {
+    let tokens = quote! {
+        <foo>"a\nb" r"c"</foo>
+    };
+
+    let nodes = parse2(tokens)?;
+    let Node::Text(escaped) = get_element_child(&nodes, 0, 0) else { panic!("expected text") };
+    let Node::Text(raw) = get_element_child(&nodes, 0, 1) else { panic!("expected text") };
+
+    assert_eq!(escaped.raw_literal(), r#""a\nb""#);
+    assert_eq!(String::try_from(&escaped.value)?, "a\nb");
+    assert_eq!(raw.raw_literal(), r#"r"c""#);
+    assert_eq!(String::try_from(&raw.value)?, "c");
+
+    Ok(())
+}
+
 #[test]
 fn test_reserved_keyword_attributes() -> Result<()> {
     let tokens = quote! {
@@ -59,7 +97,7 @@ fn test_reserved_keyword_attributes() -> Result<()> {
     };
     let nodes = parse2(tokens)?;
     let element = get_element(&nodes, 0);
-    let Some(Node::Attribute(attribute)) = element.attributes.get(0) else { panic!("expected attribute") };
+    let Some(Node::Attribute(attribute)) = element.attributes.first() else { panic!("expected attribute") };
 
     assert_eq!(element.name.to_string(), "input");
     assert_eq!(attribute.key.to_string(), "type");
@@ -142,6 +180,37 @@ fn test_block_as_tag_name_with_closing_tag() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_block_as_tag_name_with_mismatching_closing_tag() -> Result<()> {
+    let tokens = quote! {
+        <{some_logic(block)}>"Test"</{other_logic(block)}>
+    };
+
+    let error = parse2(tokens).expect_err("mismatching block names");
+
+    assert_eq!(
+        error.to_string(),
+        "close tag block name does not match open tag block name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_block_tag_name_accessor() -> Result<()> {
+    let tokens = quote! {
+        <{some_logic(block)} />
+        <div />
+    };
+
+    let nodes = parse2(tokens)?;
+
+    assert!(get_element(&nodes, 0).name.as_block().is_some());
+    assert!(get_element(&nodes, 1).name.as_block().is_none());
+
+    Ok(())
+}
+
 #[test]
 fn test_dashed_attribute_name() -> Result<()> {
     let tokens = quote! {
@@ -243,6 +312,24 @@ fn test_type_of_top_level_nodes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_template_elements() -> Result<()> {
+    let tokens = quote! {
+        <template><div /></template>
+        <div />
+    };
+
+    let config = ParserConfig::new().template_elements(HashSet::from(["template"]));
+    let nodes = parse2_with_config(tokens, config)?;
+    let template = get_element(&nodes, 0);
+
+    assert!(template.is_template());
+    assert_eq!(template.children.len(), 1);
+    assert!(!get_element(&nodes, 1).is_template());
+
+    Ok(())
+}
+
 #[test]
 fn test_transform_block_some() -> Result<()> {
     use syn::{Expr, Lit, Stmt, Token};
@@ -292,6 +379,57 @@ fn test_transform_block_none() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_transform_text() -> Result<()> {
+    let tokens = quote! {
+        <div>"hello"<span>"world"</span></div>
+    };
+
+    let config = ParserConfig::new().transform_text(|text| {
+        String::try_from(&text.value)
+            .ok()
+            .map(|text| text.to_uppercase())
+    });
+
+    let nodes = parse2_with_config(tokens, config)?;
+    let Node::Text(hello) = get_element_child(&nodes, 0, 0) else { panic!("expected text") };
+    let Node::Element(span) = get_element_child(&nodes, 0, 1) else { panic!("expected element") };
+    let Node::Text(world) = &span.children[0] else { panic!("expected text") };
+
+    assert_eq!(String::try_from(&hello.value)?, "HELLO");
+    assert_eq!(String::try_from(&world.value)?, "WORLD");
+
+    Ok(())
+}
+
+#[test]
+fn test_transform_block_if() -> Result<()> {
+    use std::{cell::Cell, rc::Rc};
+
+    use syn::Token;
+
+    let tokens = quote! {
+        <div>{%}{"foo"}{%}</div>
+    };
+
+    let calls = Rc::new(Cell::new(0));
+    let transform_calls = calls.clone();
+    let config = ParserConfig::new()
+        .transform_block(move |input| {
+            transform_calls.set(transform_calls.get() + 1);
+            input.parse::<Token![%]>()?;
+            Ok(Some(quote! { "percent" }))
+        })
+        .transform_block_if(|input| input.peek(Token![%]));
+
+    let nodes = parse2_with_config(tokens, config)?;
+
+    assert_eq!(get_element(&nodes, 0).children.len(), 3);
+    assert_eq!(calls.get(), 2);
+
+    Ok(())
+}
+
 #[test]
 fn test_doctype() -> Result<()> {
     let tokens = quote! {
@@ -301,13 +439,27 @@ fn test_doctype() -> Result<()> {
     };
 
     let nodes = parse2(tokens)?;
-    let Some(Node::Doctype(doctype)) = nodes.get(0) else { panic!("expected doctype") };
+    let Some(Node::Doctype(doctype)) = nodes.first() else { panic!("expected doctype") };
 
     assert_eq!(String::try_from(&doctype.value)?, "html");
 
     Ok(())
 }
 
+#[test]
+fn test_doctype_normalized_value() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = proc_macro2::TokenStream::from_str("<!DOCTYPE   html  >").expect("tokens");
+
+    let nodes = parse2(tokens)?;
+    let Some(Node::Doctype(doctype)) = nodes.first() else { panic!("expected doctype") };
+
+    assert_eq!(doctype.normalized_value(), "html");
+
+    Ok(())
+}
+
 #[test]
 fn test_comment() -> Result<()> {
     let tokens = quote! {
@@ -319,7 +471,7 @@ fn test_comment() -> Result<()> {
     };
 
     let nodes = parse2(tokens)?;
-    let Some(Node::Comment(comment1)) = nodes.get(0) else { panic!("expected comment") };
+    let Some(Node::Comment(comment1)) = nodes.first() else { panic!("expected comment") };
     let Node::Comment(comment2) =
         get_element_child(&nodes, 1, 0) else { panic!("expected comment") };
 
@@ -338,9 +490,98 @@ fn test_fragment() -> Result<()> {
     };
 
     let nodes = parse2(tokens)?;
-    let Some(Node::Fragment(fragment)) = nodes.get(0) else { panic!("expected fragment") };
+    let Some(Node::Fragment(fragment)) = nodes.first() else { panic!("expected fragment") };
+
+    assert_eq!(fragment.children.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_fragments() -> Result<()> {
+    let tokens = quote! {
+        <>
+            "outer"
+            <>
+                "middle"
+                <>
+                    "inner"
+                </>
+                <span />
+            </>
+            <div />
+        </>
+        <>"sibling"</>
+    };
+
+    let nodes = parse2(tokens)?;
+    assert_eq!(nodes.len(), 2);
+
+    let Node::Fragment(outer) = &nodes[0] else { panic!("expected fragment") };
+    assert_eq!(outer.children.len(), 3);
+    assert_eq!(outer.children[0].r#type(), NodeType::Text);
+    assert_eq!(outer.children[2].r#type(), NodeType::Element);
+
+    let Node::Fragment(middle) = &outer.children[1] else { panic!("expected fragment") };
+    assert_eq!(middle.children.len(), 3);
+    assert_eq!(middle.children[0].r#type(), NodeType::Text);
+    assert_eq!(middle.children[2].r#type(), NodeType::Element);
+
+    let Node::Fragment(inner) = &middle.children[1] else { panic!("expected fragment") };
+    assert_eq!(inner.children.len(), 1);
+    assert_eq!(inner.children[0].r#type(), NodeType::Text);
+
+    let Node::Fragment(sibling) = &nodes[1] else { panic!("expected fragment") };
+    assert_eq!(sibling.children.len(), 1);
+
+    let nodes = parse2(quote! { <><>"a"</>"b"</> })?;
+    let Node::Fragment(outer) = &nodes[0] else { panic!("expected fragment") };
+    assert_eq!(outer.children.len(), 2);
+    assert_eq!(outer.children[0].r#type(), NodeType::Fragment);
+
+    assert!(parse2(quote! { <><>"a"</> }).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_wrap_fragment() -> Result<()> {
+    let nodes = parse2(quote! { <div /> "text" })?;
+    let fragment = Node::wrap_fragment(nodes);
+
+    let Node::Fragment(wrapped) = &fragment else { panic!("expected fragment") };
+    assert_eq!(wrapped.children.len(), 2);
+    assert_eq!(
+        fragment.to_token_stream().to_string(),
+        quote! { <> <div /> "text" </> }.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unclosed_fragment() -> Result<()> {
+    let tokens = quote! {
+        <><div />
+    };
+
+    assert!(parse2(tokens.clone()).is_err());
 
+    let config = ParserConfig::new().allow_unclosed_fragment(true);
+    let (nodes, warnings) = syn_rsx::parse2_with_warnings(tokens, config)?;
+    let Some(Node::Fragment(fragment)) = nodes.first() else { panic!("expected fragment") };
+
+    assert_eq!(nodes.len(), 1);
     assert_eq!(fragment.children.len(), 1);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].message,
+        "fragment has no corresponding close tag `</>`"
+    );
+
+    let config = ParserConfig::new().allow_unclosed_fragment(true);
+    let (_, warnings) = syn_rsx::parse2_with_warnings(quote! { <><div /></> }, config)?;
+    assert!(warnings.is_empty());
 
     Ok(())
 }
@@ -360,25 +601,2592 @@ fn test_reserved_keywords() -> Result<()> {
     Ok(())
 }
 
-fn get_element(nodes: &[Node], element_index: usize) -> &NodeElement {
-    let Some(Node::Element(element)) = nodes.get(element_index) else { panic!("expected element") };
-    element
+#[test]
+fn test_custom_element_names_with_numbers() -> Result<()> {
+    use syn_rsx::{NodeName, NodeNameFragment};
+
+    let tokens = quote! {
+        <ion-col-2 />
+        <x-5 data-1="a" />
+        <grid-2x></grid-2x>
+        <my-el-3:foo />
+    };
+
+    let nodes = parse2(tokens)?;
+
+    assert_eq!(get_element(&nodes, 0).name.to_string(), "ion-col-2");
+    assert_eq!(get_element(&nodes, 1).name.to_string(), "x-5");
+    assert_eq!(
+        get_element_attribute(&nodes, 1, 0).key.to_string(),
+        "data-1"
+    );
+    let NodeName::Punctuated(key) = &get_element_attribute(&nodes, 1, 0).key else { panic!("expected punctuated name") };
+    let fragments = key
+        .iter()
+        .map(|fragment| match fragment {
+            NodeNameFragment::Ident(ident) => format!("ident {}", ident),
+            NodeNameFragment::Literal(literal) => format!("literal {}", literal),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fragments, vec!["ident data", "literal 1"]);
+    assert_eq!(get_element(&nodes, 2).name.to_string(), "grid-2x");
+    assert_eq!(get_element(&nodes, 3).name.to_string(), "my-el-3:foo");
+
+    assert!(parse2(quote! { <x:5 /> }).is_err());
+
+    Ok(())
 }
 
-fn get_element_attribute(
-    nodes: &[Node],
-    element_index: usize,
-    attribute_index: usize,
-) -> &NodeAttribute {
-    let Some(Node::Element(element)) =
-        nodes.get(element_index) else { panic!("expected element") };
-    let Some(Node::Attribute(attribute)) =
-        element.attributes.get(attribute_index) else { panic!("expected attribute") };
+#[test]
+fn test_attribute_order() -> Result<()> {
+    use std::str::FromStr;
 
-    attribute
+    let tokens =
+        proc_macro2::TokenStream::from_str(r#"<div c="3" a b={2} {d} />"#).expect("tokens");
+
+    let nodes = parse2(tokens)?;
+    let element = get_element(&nodes, 0);
+    let keys = element
+        .attributes
+        .iter()
+        .map(|attribute| match attribute {
+            Node::Attribute(attribute) => attribute.key.to_string(),
+            _ => "{}".to_owned(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["c", "a", "b", "{}"]);
+
+    let spans = element
+        .attribute_spans()
+        .iter()
+        .map(|span| (span.start().column, span.end().column))
+        .collect::<Vec<_>>();
+    assert_eq!(spans, [(5, 10), (11, 12), (13, 18), (19, 22)]);
+
+    Ok(())
 }
 
-fn get_element_child(nodes: &[Node], element_index: usize, child_index: usize) -> &Node {
-    let Some(Node::Element(element)) = nodes.get(element_index) else { panic!("expected element") };
-    element.children.get(child_index).expect("child")
+#[test]
+fn test_element_into_and_from_parts() -> Result<()> {
+    let tokens = quote! {
+        <div a="1">"child"</div>
+    };
+
+    let mut nodes = parse2(tokens)?;
+    let Node::Element(element) = nodes.remove(0) else { panic!("expected element") };
+    let (name, attributes, mut children, span) = element.into_parts();
+    children.clear();
+    let element = NodeElement::from_parts(name, attributes, children, span);
+
+    assert_eq!(element.name.to_string(), "div");
+    assert_eq!(element.attributes.len(), 1);
+    assert!(element.children.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_resolve_namespaces() -> Result<()> {
+    use syn_rsx::xml::{resolve_namespaces, XMLNS_NAMESPACE};
+
+    let tokens = quote! {
+        <root xmlns="urn:root" xmlns:svg="http://www.w3.org/2000/svg">
+            <svg:rect svg:width="1" height="2" />
+            <child />
+        </root>
+        <outside />
+    };
+
+    let nodes = parse2(tokens)?;
+    let namespaces = resolve_namespaces(&nodes);
+    let root = get_element(&nodes, 0);
+    let Node::Element(rect) = &root.children[0] else { panic!("expected element") };
+    let Node::Element(child) = &root.children[1] else { panic!("expected element") };
+    let Node::Attribute(xmlns) = &root.attributes[0] else { panic!("expected attribute") };
+    let Node::Attribute(width) = &rect.attributes[0] else { panic!("expected attribute") };
+    let Node::Attribute(height) = &rect.attributes[1] else { panic!("expected attribute") };
+
+    assert_eq!(namespaces.element(root), Some("urn:root"));
+    assert_eq!(namespaces.element(rect), Some("http://www.w3.org/2000/svg"));
+    assert_eq!(namespaces.element(child), Some("urn:root"));
+    assert_eq!(namespaces.element(get_element(&nodes, 1)), None);
+    assert_eq!(namespaces.attribute(xmlns), Some(XMLNS_NAMESPACE));
+    assert_eq!(
+        namespaces.attribute(width),
+        Some("http://www.w3.org/2000/svg")
+    );
+    assert_eq!(namespaces.attribute(height), None);
+
+    // Only string literals declare namespaces.
+    let nodes = parse2(quote! { <root xmlns=urn::root xmlns:svg={svg}><svg:rect /></root> })?;
+    let namespaces = resolve_namespaces(&nodes);
+    let root = get_element(&nodes, 0);
+    let Node::Element(rect) = &root.children[0] else { panic!("expected element") };
+    assert_eq!(namespaces.element(root), None);
+    assert_eq!(namespaces.element(rect), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_display() -> Result<()> {
+    let tokens = quote! {
+        <!DOCTYPE html>
+        <div a="1" b c={d}>"hi"<!-- "comment" -->{block}</div>
+    };
+
+    let nodes = parse2(tokens)?;
+    let html = nodes.iter().map(ToString::to_string).collect::<String>();
+
+    assert!(html.contains("<div a=\"1\" b c={ d }>hi"));
+    assert!(html.contains("<!-- comment -->"));
+    assert_eq!(get_element(&nodes, 1).children[0].to_string(), "hi");
+
+    let nodes = parse2(quote! { <div>"hi"</div> })?;
+    assert!(format!("{}", nodes[0]).contains("<div>hi</div>"));
+
+    Ok(())
+}
+
+#[test]
+fn test_select_find() -> Result<()> {
+    use syn_rsx::select;
+
+    let tokens = quote! {
+        <div class="active">
+            <span class="active" />
+            <div class="item active" id="nested" />
+            <div class={active} />
+        </div>
+        <>
+            <div class="inactive" />
+        </>
+    };
+
+    let nodes = parse2(tokens)?;
+
+    let active = select::find(&nodes, "div", Some("active"));
+    assert_eq!(active.len(), 2);
+    assert_eq!(active[1].attributes.len(), 2);
+    assert_eq!(select::find(&nodes, "div", None).len(), 4);
+    assert_eq!(select::find(&nodes, "span", Some("active")).len(), 1);
+    assert!(select::find(&nodes, "p", None).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_leading_comments() -> Result<()> {
+    let tokens = quote! {
+        <!-- "license" -->
+        <!-- "header" -->
+        <!DOCTYPE html>
+        <!-- "after doctype" -->
+        <html />
+    };
+
+    let nodes = parse2(tokens)?;
+    let comments = syn_rsx::leading_comments(&nodes);
+
+    assert_eq!(comments.len(), 2);
+    assert!(matches!(nodes[2], Node::Doctype(_)));
+    assert!(matches!(nodes[3], Node::Comment(_)));
+
+    let nodes = parse2(quote! { <html /> })?;
+    assert!(syn_rsx::leading_comments(&nodes).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed_input_does_not_panic() -> Result<()> {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+
+    let sources = [
+        "<",
+        "<>",
+        "</>",
+        "<div",
+        "<div>",
+        "</div>",
+        "<div></span>",
+        "<div a=></div>",
+        "<div a= />",
+        "<div =\"a\" />",
+        "<div {} {a} b={} />",
+        "<{}></{}>",
+        "<!",
+        "<!--",
+        "<!-- -->",
+        "<!DOCTYPE",
+        "<!DOCTYPE>",
+        "<!doctype html",
+        "<a-:b />",
+        "<a::b::/>",
+        "<- />",
+        "{}",
+        "{ let }",
+        "1 2 3",
+        "<div>\"a\"</div> <",
+    ];
+
+    for source in sources {
+        let tokens = TokenStream::from_str(source).expect("lexable source");
+        let _ = parse2(tokens.clone());
+        let _ = parse2_with_config(tokens.clone(), ParserConfig::new().flat_tree());
+        let _ = parse2_with_config(
+            tokens,
+            ParserConfig::new()
+                .type_of_top_level_nodes(NodeType::Element)
+                .transform_block(|_| Ok(None)),
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "span-locations")]
+fn test_parse_str_diagnostics() -> Result<()> {
+    use syn_rsx::{
+        diagnostic::{Level, SimpleDiagnostic},
+        parse_str,
+    };
+
+    let nodes = parse_str("<div>\"hi\"</div>").expect("nodes");
+    assert_eq!(nodes.len(), 1);
+
+    let diagnostics = parse_str("<div />\n  <span>").expect_err("diagnostics");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "open tag has no corresponding close tag and is not self-closing"
+    );
+    assert_eq!(diagnostics[0].level, Level::Error);
+    assert_eq!(
+        (diagnostics[0].start.line, diagnostics[0].start.column),
+        (2, 3)
+    );
+
+    let diagnostics = parse_str("<div>{</div>").expect_err("lex error");
+    assert_eq!(diagnostics.len(), 1);
+
+    let error = syn::Error::new(proc_macro2::Span::call_site(), "message");
+    assert_eq!(SimpleDiagnostic::from(&error).message, "message");
+
+    Ok(())
+}
+
+#[test]
+fn test_map_attribute_values() -> Result<()> {
+    use quote::ToTokens;
+    use syn::{parse_quote, Expr, Lit};
+
+    let tokens = quote! {
+        <a href="/home" disabled>
+            <>
+                <img src="/logo.png" width=100 />
+            </>
+        </a>
+    };
+
+    let mut nodes = parse2(tokens)?;
+    let mut keys = vec![];
+    syn_rsx::map_attribute_values(&mut nodes, |key, value| {
+        keys.push(key.to_string());
+        if let Expr::Lit(expr) = value {
+            if let Lit::Str(_) = expr.lit {
+                *value = parse_quote! { prefix(#value) };
+            }
+        }
+    });
+
+    let href = get_element_attribute(&nodes, 0, 0);
+    let Node::Fragment(fragment) = get_element_child(&nodes, 0, 0) else { panic!("expected fragment") };
+    let Node::Element(img) = &fragment.children[0] else { panic!("expected element") };
+    let Node::Attribute(width) = &img.attributes[1] else { panic!("expected attribute") };
+
+    assert_eq!(keys, ["href", "src", "width"]);
+    assert_eq!(
+        href.value
+            .as_ref()
+            .expect("value")
+            .to_token_stream()
+            .to_string(),
+        quote! { prefix("/home") }.to_string()
+    );
+    assert_eq!(
+        width
+            .value
+            .as_ref()
+            .expect("value")
+            .to_token_stream()
+            .to_string(),
+        "100"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_roundtrip() -> Result<()> {
+    assert_roundtrip(quote! {
+        <!DOCTYPE html>
+        <!doctype html>
+        <!-- "comment" -->
+        <html lang="en">
+            <head>
+                <meta charset="utf-8" />
+            </head>
+            <body>
+                <div c="3" a b={2} {d} data-foo="bar" on:click={foo} key=some::value()>
+                    "text"{block}<br/>
+                </div>
+                <></>
+                <>
+                    <some::path />
+                    <{some_logic(block)}>"Test"</{some_logic(block)}>
+                </>
+            </body>
+        </html>
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_to_token_stream() -> Result<()> {
+    use std::collections::HashMap;
+
+    use syn_rsx::ElementClassification;
+
+    let tokens = quote! {
+        <!doctype html>
+        <!-- "comment" -->
+        <div b a="1"><br /></div>
+    };
+    let nodes = parse2(tokens.clone())?;
+    let emitted = nodes
+        .iter()
+        .map(Node::to_token_stream)
+        .collect::<proc_macro2::TokenStream>();
+
+    assert_eq!(emitted.to_string(), tokens.to_string());
+
+    let config = ParserConfig::new()
+        .element_classification(HashMap::from([("br", ElementClassification::Void)]));
+    let nodes = parse2_with_config(quote! { <p><br>"a"<br /><br></br></p> }, config)?;
+    assert_eq!(
+        nodes[0].to_token_stream().to_string(),
+        quote! { <p><br>"a"<br /><br></p> }.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_directives() -> Result<()> {
+    let tokens = quote! {
+        <div class:active={flag} style:color={color} on:click={handler} data-foo="bar" />
+    };
+
+    let config = ParserConfig::new().attribute_directives(HashSet::from(["class", "style"]));
+    let nodes = parse2_with_config(tokens, config)?;
+
+    let directive = |index| {
+        get_element_attribute(&nodes, 0, index)
+            .directive()
+            .map(|(directive, arg)| (directive.to_string(), arg.to_string()))
+    };
+    assert_eq!(
+        directive(0),
+        Some(("class".to_owned(), "active".to_owned()))
+    );
+    assert_eq!(directive(1), Some(("style".to_owned(), "color".to_owned())));
+    assert_eq!(directive(2), None);
+    assert_eq!(directive(3), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_text_content() -> Result<()> {
+    let tokens = quote! {
+        <div>"a "<span>"b"{block}</span><>" c"</><!-- "comment" --> 1</div>
+    };
+
+    let nodes = parse2(tokens)?;
+    let element = get_element(&nodes, 0);
+
+    assert_eq!(element.text_content(), "a b c1");
+
+    Ok(())
+}
+
+#[test]
+fn test_self_closing_slash_span() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = proc_macro2::TokenStream::from_str("<div/><div></div>").expect("tokens");
+
+    let nodes = parse2(tokens)?;
+
+    let span = get_element(&nodes, 0)
+        .self_closing_slash_span()
+        .expect("slash span");
+    assert_eq!(span.start().column, 4);
+    assert!(get_element(&nodes, 1).self_closing_slash_span().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_write_html() -> Result<()> {
+    let tokens = quote! {
+        <!DOCTYPE html>
+        <html>
+            <body class="main" hidden>
+                <!-- "comment" -->
+                <h1>"Title"</h1>
+                <>"Fragment"{block}</>
+            </body>
+        </html>
+    };
+
+    let nodes = parse2(tokens)?;
+    let mut html = vec![];
+    syn_rsx::html::write_html(&mut html, &nodes)?;
+
+    let expected = nodes.iter().map(ToString::to_string).collect::<String>();
+    assert_eq!(String::from_utf8(html)?, expected);
+
+    let config = ParserConfig::new().element_classification(
+        vec![("br", syn_rsx::ElementClassification::Void)]
+            .into_iter()
+            .collect(),
+    );
+    let tokens = quote! { <p title="\"&">"a<br>"<br>"<b>"</p> };
+    let nodes = parse2_with_config(tokens, config)?;
+    let mut html = vec![];
+    syn_rsx::html::write_html(&mut html, &nodes)?;
+    assert_eq!(
+        String::from_utf8(html)?,
+        r#"<p title="&quot;&amp;">a&lt;br&gt;<br>&lt;b&gt;</p>"#
+    );
+
+    let nodes = parse2(quote! { <!-- "a-->b<!--c---" --> })?;
+    let mut html = vec![];
+    syn_rsx::html::write_html(&mut html, &nodes)?;
+    assert_eq!(String::from_utf8(html)?, "<!-- a- ->b<!- -c- - - -->");
+
+    Ok(())
+}
+
+#[test]
+fn test_element_classification() -> Result<()> {
+    use std::collections::HashMap;
+
+    use syn_rsx::ElementClassification;
+
+    let config = || {
+        ParserConfig::new().element_classification(HashMap::from([
+            ("br", ElementClassification::Void),
+            ("script", ElementClassification::RawText),
+            ("textarea", ElementClassification::EscapableRawText),
+        ]))
+    };
+
+    let tokens = quote! {
+        <div><br><br /><br></br>"text"</div>
+        <script>"let x = 1;"</script>
+        <textarea>"Hello "{name}</textarea>
+        <p><span>"normal"</span>{block}</p>
+    };
+    let nodes = parse2_with_config(tokens, config())?;
+    let div = get_element(&nodes, 0);
+
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(div.children.len(), 4);
+    assert!(matches!(div.children[3], Node::Text(_)));
+    assert_eq!(get_element(&nodes, 1).children.len(), 1);
+    assert_eq!(get_element(&nodes, 2).children.len(), 2);
+    assert_eq!(get_element(&nodes, 3).children.len(), 2);
+
+    let tokens = quote! { <script>{block}</script> };
+    assert!(parse2_with_config(tokens, config()).is_err());
+
+    let tokens = quote! { <textarea><b>"bold"</b></textarea> };
+    assert!(parse2_with_config(tokens, config()).is_err());
+
+    let tokens = quote! { <div><br></div> };
+    assert!(parse2(tokens).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_reparse() -> Result<()> {
+    let tokens = quote! {
+        <div key="value"><span /></div>
+    };
+
+    let mut nodes = parse2(tokens)?;
+    let Some(Node::Element(element)) = nodes.first_mut() else { panic!("expected element") };
+    element.name = syn_rsx::NodeName::Path(syn::parse_quote! { section });
+
+    let Node::Element(reparsed) = nodes[0].reparse(&ParserConfig::new())? else { panic!("expected element") };
+    assert_eq!(reparsed.name.to_string(), "section");
+    assert_eq!(reparsed.children.len(), 1);
+
+    let attribute = &get_element(&nodes, 0).attributes[0];
+    assert!(matches!(
+        attribute.reparse(&ParserConfig::new())?,
+        Node::Attribute(_)
+    ));
+
+    let config = ParserConfig::new().allowed_elements(Some(HashSet::from(["div", "span"])));
+    let error = nodes[0].reparse(&config).unwrap_err();
+    assert_eq!(error.to_string(), "element `section` is not allowed");
+
+    Ok(())
+}
+
+#[test]
+fn test_shorthand_attributes() -> Result<()> {
+    let tokens = quote! {
+        <input {value} {a + b} {some::path} />
+    };
+
+    let config = ParserConfig::new().shorthand_attributes(true);
+    let nodes = parse2_with_config(tokens, config)?;
+    let attributes = &get_element(&nodes, 0).attributes;
+
+    let Node::Attribute(attribute) = &attributes[0] else { panic!("expected attribute") };
+    assert_eq!(attribute.key.to_string(), "value");
+    assert_eq!(attribute.to_string(), "value={value}");
+    assert!(matches!(attributes[1], Node::Block(_)));
+    assert!(matches!(attributes[2], Node::Block(_)));
+
+    let nodes = parse2(quote! { <input {value} /> })?;
+    assert!(matches!(
+        get_element(&nodes, 0).attributes[0],
+        Node::Block(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_content_hash() -> Result<()> {
+    use std::str::FromStr;
+
+    let first = parse2(quote! { <div class="a">"text"{block}<br /></div> })?;
+    let second = parse2(
+        proc_macro2::TokenStream::from_str(
+            "\n\n    <div   class = \"a\" >\"text\" { block } <br/></div>",
+        )
+        .expect("tokens"),
+    )?;
+    let different = parse2(quote! { <div class="b">"text"{block}<br /></div> })?;
+
+    assert_eq!(first[0].content_hash(), second[0].content_hash());
+    assert_ne!(first[0].content_hash(), different[0].content_hash());
+
+    Ok(())
+}
+
+#[test]
+fn test_allowlists() -> Result<()> {
+    let config = || {
+        ParserConfig::new()
+            .allowed_elements(Some(HashSet::from(["div", "p"])))
+            .allowed_attributes(Some(HashSet::from(["class"])))
+    };
+
+    let tokens = quote! { <div class="a" {props}><p>"text"</p></div> };
+    assert!(parse2_with_config(tokens, config()).is_ok());
+
+    let tokens = quote! { <div><script>"alert(1)"</script></div> };
+    let error = parse2_with_config(tokens, config()).unwrap_err();
+    assert_eq!(error.to_string(), "element `script` is not allowed");
+
+    let tokens = quote! { <div onclick="alert(1)" /> };
+    let error = parse2_with_config(tokens, config()).unwrap_err();
+    assert_eq!(error.to_string(), "attribute `onclick` is not allowed");
+
+    let tokens = quote! { <input {class} {onclick} /> };
+    let error = parse2_with_config(tokens, config().shorthand_attributes(true)).unwrap_err();
+    assert_eq!(error.to_string(), "attribute `onclick` is not allowed");
+
+    Ok(())
+}
+
+#[test]
+fn test_source_map() -> Result<()> {
+    use std::str::FromStr;
+
+    use syn_rsx::html::{to_html_with_source_map, write_html};
+
+    let tokens =
+        proc_macro2::TokenStream::from_str(r#"<ul class="list"><li>"one"</li><>{two}</></ul>"#)
+            .expect("tokens");
+
+    let nodes = parse2(tokens)?;
+    let (html, source_map) = to_html_with_source_map(&nodes);
+    let mut expected = vec![];
+    write_html(&mut expected, &nodes)?;
+    assert_eq!(html.as_bytes(), expected);
+    assert_eq!(html, r#"<ul class="list"><li>one</li>{ two }</ul>"#);
+
+    let entries = source_map
+        .entries()
+        .iter()
+        .map(|(range, span)| (range.clone(), span.start().column))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            (0..41, 0),
+            (4..16, 4),
+            (17..29, 17),
+            (21..24, 21),
+            (29..36, 31),
+            (29..36, 33),
+        ]
+    );
+    let column_at = |offset| source_map.span_at(offset).map(|span| span.start().column);
+    assert_eq!(column_at(22), Some(21));
+    assert_eq!(column_at(30), Some(33));
+    // Offsets in close tags belong to their element.
+    assert_eq!(column_at(25), Some(17));
+    assert_eq!(column_at(38), Some(0));
+    assert_eq!(column_at(41), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_foreign_content_elements() -> Result<()> {
+    let tokens = quote! {
+        <div>
+            <svg viewBox="0 0 10 10"><g><rect/></g></svg>
+            <span />
+        </div>
+    };
+
+    let config = ParserConfig::new().foreign_content_elements(HashSet::from(["svg", "math"]));
+    let nodes = parse2_with_config(tokens, config)?;
+
+    let div = get_element(&nodes, 0);
+    let Node::Element(svg) = get_element_child(&nodes, 0, 0) else { panic!("expected element") };
+    let Node::Element(g) = &svg.children[0] else { panic!("expected element") };
+    let Node::Element(rect) = &g.children[0] else { panic!("expected element") };
+    let Node::Element(span) = get_element_child(&nodes, 0, 1) else { panic!("expected element") };
+
+    assert!(!div.is_foreign_content());
+    assert!(svg.is_foreign_content());
+    assert!(g.is_foreign_content());
+    assert!(rect.is_foreign_content());
+    assert!(!span.is_foreign_content());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "span-locations")]
+fn test_parse_str_chunked() -> Result<()> {
+    use syn_rsx::parse_str_chunked;
+
+    let source = "<div>\n    \"first\"\n</div>\n\n<p>{ \"unclosed\"</p>\n<span class=\"last\" />\n";
+    let chunks = parse_str_chunked(source, ParserConfig::new());
+    assert_eq!(chunks.len(), 1);
+    assert!(chunks[0].is_err());
+
+    let chunks = parse_str_chunked(source, ParserConfig::new().chunked_recovery(true));
+    assert_eq!(chunks.len(), 3);
+
+    let Ok(first) = &chunks[0] else { panic!("expected nodes") };
+    assert_eq!(first[0].to_string(), "<div>first</div>");
+
+    let Err(diagnostics) = &chunks[1] else { panic!("expected diagnostics") };
+    assert_eq!(diagnostics[0].start.line, 5);
+
+    let Ok(last) = &chunks[2] else { panic!("expected nodes") };
+    assert_eq!(syn::spanned::Spanned::span(&last[0]).start().line, 6);
+
+    let source = "<div>\n    \"first\"\n</div>\n<span />\n";
+    let chunks = parse_str_chunked(source, ParserConfig::new().chunked_recovery(true));
+    assert_eq!(chunks.len(), 1);
+    let Ok(nodes) = &chunks[0] else { panic!("expected nodes") };
+    assert_eq!(nodes.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_value_span() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = proc_macro2::TokenStream::from_str("<div x = foo() hidden />").expect("tokens");
+
+    let nodes = parse2(tokens)?;
+    let attribute = get_element_attribute(&nodes, 0, 0);
+
+    let value_span = attribute.value_span().expect("value span");
+    assert_eq!(
+        (value_span.start().column, value_span.end().column),
+        (9, 14)
+    );
+    let eq_value_span = attribute.eq_value_span().expect("eq value span");
+    assert_eq!(
+        (eq_value_span.start().column, eq_value_span.end().column),
+        (7, 14)
+    );
+
+    let attribute = get_element_attribute(&nodes, 0, 1);
+    assert!(attribute.value_span().is_none());
+    assert!(attribute.eq_value_span().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_call_site_span() -> Result<()> {
+    use std::str::FromStr;
+
+    let outer = proc_macro2::TokenStream::from_str("\n\nmacro_call!").expect("tokens");
+    let call_site = outer.into_iter().next().expect("token").span();
+
+    let config = ParserConfig::new().call_site_span(call_site);
+    let error = parse2_with_config(quote! { <><div /> }, config).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected end of input");
+    assert_eq!(error.span().start().line, 3);
+
+    let config = ParserConfig::new()
+        .number_of_top_level_nodes(2)
+        .call_site_span(call_site);
+    let error = parse2_with_config(quote! { <div /> }, config).unwrap_err();
+    assert_eq!(error.span().start().line, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_event_handlers() -> Result<()> {
+    use quote::ToTokens;
+    use syn_rsx::EventStyle;
+
+    let tokens = quote! {
+        <button on:click={increment} onClick={decrement} onmouseover={hover} on:blur on="x" class="y" />
+    };
+
+    let nodes = parse2(tokens)?;
+    let styles = [EventStyle::Colon("on"), EventStyle::Prefix("on")];
+    let event = |index| {
+        get_element_attribute(&nodes, 0, index)
+            .as_event_handler(&styles)
+            .map(|event| (event.name, event.handler.to_token_stream().to_string()))
+    };
+
+    assert_eq!(
+        event(0),
+        Some(("click".to_owned(), "{ increment }".to_owned()))
+    );
+    assert_eq!(
+        event(1),
+        Some(("click".to_owned(), "{ decrement }".to_owned()))
+    );
+    assert_eq!(
+        event(2),
+        Some(("mouseover".to_owned(), "{ hover }".to_owned()))
+    );
+    assert_eq!(event(3), None);
+    assert_eq!(event(4), None);
+    assert_eq!(event(5), None);
+
+    let attribute = get_element_attribute(&nodes, 0, 0);
+    assert!(attribute
+        .as_event_handler(&[EventStyle::Prefix("on")])
+        .is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_find_recursive_components() -> Result<()> {
+    use std::str::FromStr;
+
+    use syn_rsx::{find_recursive_components, NodeName};
+
+    let tokens = proc_macro2::TokenStream::from_str(
+        "<div><Foo /><Bar><><Foo>\"nested\"</Foo></></Bar></div>",
+    )
+    .expect("tokens");
+
+    let nodes = parse2(tokens)?;
+    let spans = find_recursive_components(&nodes, &NodeName::Path(syn::parse_quote! { Foo }));
+    let columns = spans
+        .iter()
+        .map(|span| span.start().column)
+        .collect::<Vec<_>>();
+    assert_eq!(columns, vec![5, 19]);
+
+    let spans = find_recursive_components(&nodes, &NodeName::Path(syn::parse_quote! { Baz }));
+    assert!(spans.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_text_interpolation() -> Result<()> {
+    use syn_rsx::InterpolationSyntax;
+
+    let tokens = quote! {
+        <p>"Hello ${name}, you have ${ items.iter().map(|x| { x.len() }).sum::<usize>() } items"</p>
+        <p>"${first}${second}"</p>
+        <p>"no interpolation"</p>
+    };
+
+    let config = ParserConfig::new().text_interpolation(Some(InterpolationSyntax::DollarBrace));
+    let nodes = parse2_with_config(tokens, config)?;
+
+    let children = |index| {
+        get_element(&nodes, index)
+            .children
+            .iter()
+            .map(|child| (child.r#type(), child.to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        children(0),
+        vec![
+            (NodeType::Text, "Hello ".to_owned()),
+            (NodeType::Block, "{ name }".to_owned()),
+            (NodeType::Text, ", you have ".to_owned()),
+            (
+                NodeType::Block,
+                "{ items . iter () . map (| x | { x . len () }) . sum :: < usize > () }".to_owned()
+            ),
+            (NodeType::Text, " items".to_owned()),
+        ]
+    );
+    assert_eq!(
+        children(1),
+        vec![
+            (NodeType::Block, "{ first }".to_owned()),
+            (NodeType::Block, "{ second }".to_owned()),
+        ]
+    );
+    assert_eq!(
+        children(2),
+        vec![(NodeType::Text, "no interpolation".to_owned())]
+    );
+
+    let config = ParserConfig::new().text_interpolation(Some(InterpolationSyntax::DoubleBrace));
+    let nodes = parse2_with_config(quote! { "a {{ b }} c" }, config)?;
+    assert_eq!(nodes.len(), 3);
+
+    let config = ParserConfig::new().text_interpolation(Some(InterpolationSyntax::DollarBrace));
+    assert!(parse2_with_config(quote! { "a ${ b" }, config).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_consumes_entire_input() -> Result<()> {
+    use syn::parse::{ParseStream, Parser as _};
+    use syn_rsx::Parser;
+
+    let parser = Parser::new(ParserConfig::new());
+    let nodes = (|input: ParseStream| {
+        let nodes = parser.parse(input)?;
+        assert!(input.is_empty());
+        Ok(nodes)
+    })
+    .parse2(quote! { <div /> "text" {block} })?;
+    assert_eq!(nodes.len(), 3);
+
+    let error = parse2(quote! { <div /> </div> }).unwrap_err();
+    assert_eq!(error.to_string(), "close tag has no corresponding open tag");
+
+    let error = parse2(quote! { <div /> + }).unwrap_err();
+    assert_eq!(error.to_string(), "expected literal");
+
+    Ok(())
+}
+
+#[test]
+fn test_enforce_naming_convention() -> Result<()> {
+    use syn_rsx::parse2_with_warnings;
+
+    let tokens = quote! {
+        <div>
+            <Card />
+            <ui::Button />
+            <ui::button />
+            <Div />
+            <data-foo />
+        </div>
+    };
+
+    let config = ParserConfig::new().enforce_naming_convention(true);
+    let (nodes, warnings) = parse2_with_warnings(tokens.clone(), config)?;
+    let messages = warnings
+        .iter()
+        .map(|warning| warning.message.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(get_element(&nodes, 0).children.len(), 5);
+    assert_eq!(
+        messages,
+        vec![
+            "component `ui::button` should start with an uppercase letter",
+            "`Div` is ambiguous with the HTML element `div`",
+        ]
+    );
+
+    let (_, warnings) = parse2_with_warnings(tokens, ParserConfig::new())?;
+    assert!(warnings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_negation() -> Result<()> {
+    let tokens = quote! {
+        <input !checked disabled !data-foo={x} />
+    };
+
+    assert!(parse2(tokens.clone()).is_err());
+
+    let config = ParserConfig::new().allow_attribute_negation(true);
+    let nodes = parse2_with_config(tokens.clone(), config)?;
+
+    let attribute = get_element_attribute(&nodes, 0, 0);
+    assert!(attribute.is_negated());
+    assert_eq!(attribute.key.to_string(), "checked");
+    assert!(!get_element_attribute(&nodes, 0, 1).is_negated());
+    assert!(get_element_attribute(&nodes, 0, 2).is_negated());
+
+    assert_eq!(nodes[0].to_token_stream().to_string(), tokens.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_lit_str() -> Result<()> {
+    use std::str::FromStr;
+
+    use syn_rsx::parse_lit_str;
+
+    let lit = syn::parse2::<syn::LitStr>(
+        proc_macro2::TokenStream::from_str("\n  r#\"<a href=\"x\">\"y\"</a>\"#").expect("tokens"),
+    )?;
+
+    let nodes = parse_lit_str(&lit)?;
+    let element = get_element(&nodes, 0);
+
+    assert_eq!(element.name.to_string(), "a");
+    assert_eq!(
+        get_element_attribute(&nodes, 0, 0).to_string(),
+        "href=\"x\""
+    );
+    assert_eq!(element.text_content(), "y");
+    assert_eq!(element.span.start().line, 2);
+
+    let lit = syn::parse2::<syn::LitStr>(quote! { "<a>" })?;
+    assert!(parse_lit_str(&lit).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_dynamic_parts() -> Result<()> {
+    use quote::ToTokens;
+    use syn_rsx::{dynamic_parts, DynamicPart};
+
+    let tokens = quote! {
+        <div class="static" key={value} other=some_fn() {..props} {attrs}>
+            "text"
+            {child}
+            <{tag}><>{nested}</></{tag}>
+        </div>
+        {top_level}
+    };
+
+    let nodes = parse2(tokens)?;
+    let parts = dynamic_parts(&nodes)
+        .iter()
+        .map(|part| {
+            let kind = match part {
+                DynamicPart::ChildBlock { .. } => "child",
+                DynamicPart::AttributeValue { key, .. } => {
+                    assert!(["key", "other"].contains(&key.to_string().as_str()));
+                    "value"
+                }
+                DynamicPart::SpreadAttribute { .. } => "spread",
+                DynamicPart::BlockAttribute { .. } => "block",
+                DynamicPart::BlockTagName { .. } => "tag",
+            };
+            (kind, part.expr().to_token_stream().to_string())
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        parts,
+        vec![
+            ("value", "{ value }".to_owned()),
+            ("value", "some_fn ()".to_owned()),
+            ("spread", "{ .. props }".to_owned()),
+            ("block", "{ attrs }".to_owned()),
+            ("child", "{ child }".to_owned()),
+            ("tag", "{ tag }".to_owned()),
+            ("child", "{ nested }".to_owned()),
+            ("child", "{ top_level }".to_owned()),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_misplaced_attributes() -> Result<()> {
+    let tokens = quote! {
+        <div> class="x"</div>
+    };
+
+    let error = parse2(tokens.clone()).unwrap_err();
+    assert_eq!(error.to_string(), "expected literal");
+
+    let config = ParserConfig::new().detect_misplaced_attributes(true);
+    let error = parse2_with_config(tokens, config).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "attribute is outside of the open tag, move it before the closing `>`"
+    );
+
+    let config = ParserConfig::new().detect_misplaced_attributes(true);
+    assert!(parse2_with_config(quote! { <div>"class=x"</div> }, config).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_codegen() -> Result<()> {
+    use syn_rsx::{
+        codegen::{generate, Codegen, FormatStringCodegen},
+        NodeAttribute, NodeBlock, NodeComment, NodeDoctype, NodeText,
+    };
+
+    let tokens = quote! {
+        <!DOCTYPE html>
+        <div hello={world} {attrs}>
+            <!-- "comment" -->
+            <br>
+            <>"text"{block}</>
+        </div>
+    };
+    let nodes = parse2_with_config(
+        tokens,
+        ParserConfig::new().element_classification(std::collections::HashMap::from([(
+            "br",
+            syn_rsx::ElementClassification::Void,
+        )])),
+    )?;
+
+    assert_eq!(
+        generate(FormatStringCodegen::new(), &nodes).to_string(),
+        quote! {
+            format!(
+                "<!DOCTYPE html><div hello=\"{}\" {}><!-- {} --><br>{}{}</div>",
+                { world }, { attrs }, "comment", "text", { block }
+            )
+        }
+        .to_string()
+    );
+
+    let block_name = parse2(quote! { <{tag} data-x="{}">"a"</{tag}> })?;
+    assert_eq!(
+        generate(FormatStringCodegen::new(), &block_name).to_string(),
+        quote! { format!("<{} data-x=\"{}\">{}</{}>", { tag }, "{}", "a", { tag }) }.to_string()
+    );
+
+    let block_doctype = vec![Node::Doctype(NodeDoctype::new(
+        syn_rsx::NodeValueExpr::new(syn::parse_quote! { { doctype } }),
+        proc_macro2::Span::call_site(),
+    ))];
+    assert_eq!(
+        generate(FormatStringCodegen::new(), &block_doctype).to_string(),
+        quote! { compile_error! { "doctype value needs to be a string or path" } }.to_string()
+    );
+
+    /// Collects the element names as builder calls.
+    #[derive(Default)]
+    struct Builder(Vec<proc_macro2::TokenStream>);
+
+    impl Codegen for Builder {
+        fn finish(self) -> proc_macro2::TokenStream {
+            let calls = self.0;
+            quote! { #(#calls;)* }
+        }
+
+        fn doctype(&mut self, _: &NodeDoctype) {}
+
+        fn element_open(&mut self, element: &NodeElement) {
+            let name = element.name.to_string();
+            self.0.push(quote! { open(#name) });
+        }
+
+        fn element_open_end(&mut self, _: &NodeElement) {}
+
+        fn element_close(&mut self, _: &NodeElement) {
+            self.0.push(quote! { close() });
+        }
+
+        fn attribute(&mut self, _: &NodeAttribute) {}
+
+        fn block_attribute(&mut self, _: &NodeBlock) {}
+
+        fn text(&mut self, _: &NodeText) {}
+
+        fn comment(&mut self, _: &NodeComment) {}
+
+        fn child(&mut self, _: &NodeBlock) {}
+    }
+
+    assert_eq!(
+        generate(Builder::default(), &nodes).to_string(),
+        quote! { open("div"); open("br"); close(); }.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_stats() -> Result<()> {
+    use syn_rsx::{parse2_with_stats, Parser};
+
+    let tokens = quote! {
+        <div class="a" hidden>
+            <span>"text"</span>
+            {block}
+        </div>
+    };
+
+    let (_, stats) = parse2_with_stats(tokens, ParserConfig::new())?;
+
+    // div, 2 attributes, span, text, block
+    assert_eq!(stats.nodes, 6);
+    // open div, open span, text, close span, block, close div
+    assert_eq!(stats.tokens, 7 + 3 + 1 + 4 + 2 + 4);
+    assert!(stats.forks > stats.nodes);
+
+    assert!(Parser::new(ParserConfig::new()).stats().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_single_root_element() -> Result<()> {
+    let config = || ParserConfig::new().single_root_element(true);
+
+    assert!(parse2_with_config(quote! { <!-- "c" --><div /> }, config()).is_ok());
+    assert!(parse2_with_config(quote! { <!DOCTYPE html><html></html> }, config()).is_ok());
+    assert!(parse2_with_config(quote! { <><div /><div /></> }, config()).is_ok());
+
+    let error = parse2_with_config(quote! { <div /><div /> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a single root element, wrap the elements in a fragment"
+    );
+
+    let error = parse2_with_config(quote! { <div />"text" }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a single root element, found NodeType::Text"
+    );
+
+    let error = parse2_with_config(quote! { <!-- "c" --> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected end of input, expected a single root element"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_strings() -> Result<()> {
+    let tokens = quote! {
+        <div title={title}>
+            "Welcome"
+            <img alt="Logo" src="/logo.png" />
+            <input placeholder="Search" />
+            {"dynamic"}
+        </div>
+    };
+    let nodes = parse2(tokens)?;
+
+    let strings = syn_rsx::extract_strings(&nodes, &["alt", "title"]);
+    let values: Vec<_> = strings.iter().map(|(value, _)| value.as_str()).collect();
+    assert_eq!(values, vec!["Welcome", "Logo"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_forbidden_nesting() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = proc_macro2::TokenStream::from_str(
+        r#"<a href="/"><span><a href="/nested">"link"</a></span></a><span><div /></span>"#,
+    )
+    .expect("tokens");
+    let config = ParserConfig::new().forbidden_nesting(vec![("a", "a"), ("span", "div")]);
+    let (nodes, warnings) = syn_rsx::parse2_with_warnings(tokens, config)?;
+
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0].message, "`<a>` must not be nested inside `<a>`");
+    assert_eq!(
+        warnings[1].message,
+        "`<div>` must not be nested inside `<span>`"
+    );
+
+    let nested = &get_element(&nodes, 0).children[0].children().unwrap()[0];
+    let span = syn::spanned::Spanned::span(nested);
+    assert_eq!(warnings[0].span.start(), span.start());
+    assert_eq!(warnings[0].span.end(), span.end());
+
+    Ok(())
+}
+
+#[test]
+fn test_attach_leading_comments() -> Result<()> {
+    let tokens = quote! {
+        <!-- "first" -->
+        <!-- "second" -->
+        <div />
+        "text"
+        <!-- "trailing" -->
+    };
+    let nodes = parse2(tokens)?;
+    let annotated = syn_rsx::attach_leading_comments(nodes);
+
+    assert_eq!(annotated.len(), 3);
+    assert_eq!(annotated[0].node.r#type(), NodeType::Element);
+    let comments: Vec<_> = annotated[0]
+        .comments
+        .iter()
+        .map(|comment| String::try_from(&comment.value))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(comments, vec!["first", "second"]);
+    assert_eq!(annotated[1].node.r#type(), NodeType::Text);
+    assert_eq!(annotated[2].node.r#type(), NodeType::Comment);
+    assert!(annotated[2].comments.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_max_attributes_per_element() -> Result<()> {
+    let config = || ParserConfig::new().max_attributes_per_element(3);
+
+    assert!(parse2_with_config(quote! { <div a=1 b=2 c=3 /> }, config()).is_ok());
+
+    let error = parse2_with_config(quote! { <div a=1 b=2 c=3 d=4 /> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "elements can't have more than 3 attributes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_push_str_backend() -> Result<()> {
+    let tokens = quote! {
+        <div class="x" id={id}>
+            <!-- "comment" -->
+            <img src="a.png" />
+            "text"{block}
+        </div>
+    };
+    let config = ParserConfig::new().element_classification(
+        vec![("img", syn_rsx::ElementClassification::Void)]
+            .into_iter()
+            .collect(),
+    );
+    let nodes = parse2_with_config(tokens, config)?;
+
+    assert_eq!(
+        syn_rsx::codegen::push_str_backend(&nodes).to_string(),
+        quote! {
+            {
+                let mut __html = ::std::string::String::with_capacity(64usize);
+                __html.push_str("<div class=\"x\" id=\"");
+                __html.push_str(&::std::string::ToString::to_string(&{ id }));
+                __html.push_str("\"><!-- comment --><img src=\"a.png\">text");
+                __html.push_str(&::std::string::ToString::to_string(&{ block }));
+                __html.push_str("</div>");
+                __html
+            }
+        }
+        .to_string()
+    );
+
+    let nodes = parse2(quote! { <{tag}>"a"</{tag}> })?;
+    assert_eq!(
+        syn_rsx::codegen::push_str_backend(&nodes).to_string(),
+        quote! {
+            {
+                let mut __html = ::std::string::String::with_capacity(6usize);
+                __html.push_str("<");
+                __html.push_str(&::std::string::ToString::to_string(&{ tag }));
+                __html.push_str(">a</");
+                __html.push_str(&::std::string::ToString::to_string(&{ tag }));
+                __html.push_str(">");
+                __html
+            }
+        }
+        .to_string()
+    );
+
+    let nodes = vec![Node::Doctype(syn_rsx::NodeDoctype::new(
+        syn_rsx::NodeValueExpr::new(syn::parse_quote! { { doctype } }),
+        proc_macro2::Span::call_site(),
+    ))];
+    assert!(syn_rsx::codegen::push_str_backend(&nodes)
+        .to_string()
+        .starts_with("compile_error !"));
+
+    Ok(())
+}
+
+#[test]
+fn test_escapable_raw_text_classification() -> Result<()> {
+    use syn_rsx::ElementClassification;
+
+    let tokens = quote! { <head><title>"A &amp; B"</title></head> };
+    let config = ParserConfig::new().element_classification(std::collections::HashMap::from([(
+        "title",
+        ElementClassification::EscapableRawText,
+    )]));
+    let nodes = parse2_with_config(tokens, config)?;
+
+    let head = get_element(&nodes, 0);
+    assert_eq!(head.classification(), ElementClassification::Normal);
+    let Node::Element(title) = &head.children[0] else { panic!("expected element") };
+    assert_eq!(
+        title.classification(),
+        ElementClassification::EscapableRawText
+    );
+    assert_eq!(title.text_content(), "A &amp; B");
+
+    Ok(())
+}
+
+#[test]
+fn test_original_tokens() -> Result<()> {
+    use syn::parse::Parser as _;
+    use syn_rsx::Parser;
+
+    let tokens = quote! { <div class="x">{value}</div> };
+    let parser = Parser::new(ParserConfig::new().retain_original_tokens(true));
+    assert!(parser.original_tokens().is_none());
+
+    (|input: syn::parse::ParseStream| parser.parse(input)).parse2(tokens.clone())?;
+    assert_eq!(
+        parser.original_tokens().map(|tokens| tokens.to_string()),
+        Some(tokens.to_string())
+    );
+
+    let parser = Parser::new(ParserConfig::new());
+    (|input: syn::parse::ParseStream| parser.parse(input)).parse2(tokens)?;
+    assert!(parser.original_tokens().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_block_brace_span() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = proc_macro2::TokenStream::from_str("<div>{ x }{%}</div>").expect("tokens");
+    let config = ParserConfig::new().transform_block(|input| {
+        if input.peek(syn::Token![%]) {
+            input.parse::<syn::Token![%]>()?;
+            return Ok(Some(quote! { "percent" }));
+        }
+        Ok(None)
+    });
+
+    let nodes = parse2_with_config(tokens, config)?;
+    for (index, (start, end)) in [(5, 10), (10, 13)].iter().copied().enumerate() {
+        let Node::Block(block) = get_element_child(&nodes, 0, index) else { panic!("expected block") };
+        assert_eq!(block.brace_span().start().column, start);
+        assert_eq!(block.brace_span().end().column, end);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_require_braced_attribute_values() -> Result<()> {
+    let config = || ParserConfig::new().require_braced_attribute_values(true);
+
+    assert!(parse2_with_config(quote! { <div k={foo()} l="x" m=1 /> }, config()).is_ok());
+
+    let error = parse2_with_config(quote! { <div k=foo() /> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "attribute values that aren't literals need to be wrapped in braces"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_collect_element_names() -> Result<()> {
+    let tokens = quote! {
+        <div>
+            <ui::Button />
+            <>
+                <span><ui::Button /></span>
+            </>
+        </div>
+        <span />
+    };
+    let nodes = parse2(tokens)?;
+
+    assert_eq!(
+        syn_rsx::collect_element_names(&nodes),
+        HashSet::from(["div".to_owned(), "ui::Button".to_owned(), "span".to_owned()])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_block_tag_name() -> Result<()> {
+    use syn::{Expr, Stmt};
+
+    let config = || {
+        ParserConfig::new().validate_block_tag_name(|block| match block.stmts.as_slice() {
+            [Stmt::Expr(Expr::Path(_))] => Ok(()),
+            _ => Err("expected a path like `{ui::Button}`".to_owned()),
+        })
+    };
+
+    assert!(parse2_with_config(quote! { <{ui::Button}></{ui::Button}> }, config()).is_ok());
+
+    let error = parse2_with_config(quote! { <{make()} /> }, config()).unwrap_err();
+    assert_eq!(error.to_string(), "expected a path like `{ui::Button}`");
+
+    Ok(())
+}
+
+#[test]
+fn test_attributes_map() -> Result<()> {
+    use quote::ToTokens;
+
+    let tokens = quote! { <div a=1 b c={d} {props} a=2 /> };
+    let nodes = parse2(tokens)?;
+    let map = get_element(&nodes, 0).attributes_map();
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(
+        map["a"].map(|value| value.to_token_stream().to_string()),
+        Some("2".to_owned())
+    );
+    assert!(map["b"].is_none());
+    assert_eq!(
+        map["c"].map(|value| value.to_token_stream().to_string()),
+        Some("{ d }".to_owned())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_doctype_internal_subset() -> Result<()> {
+    let tokens = quote! {
+        <!DOCTYPE root [<!ENTITY x "y">]>
+        <root />
+    };
+    let nodes = parse2(tokens.clone())?;
+
+    let Node::Doctype(doctype) = &nodes[0] else { panic!("expected doctype") };
+    assert_eq!(doctype.normalized_value(), "root");
+    assert_eq!(
+        doctype
+            .internal_subset()
+            .map(|group| group.stream().to_string()),
+        Some(quote! { <!ENTITY x "y"> }.to_string())
+    );
+    assert_eq!(nodes[1].r#type(), NodeType::Element);
+    assert_roundtrip(tokens);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse2_to_tokens() -> Result<()> {
+    let codegen = |nodes: Vec<Node>| {
+        let count = nodes.len();
+        quote! { #count }
+    };
+
+    let tokens = syn_rsx::parse2_to_tokens(quote! { <a /><b /> }, ParserConfig::new(), codegen);
+    assert_eq!(tokens.to_string(), "2usize");
+
+    let tokens = syn_rsx::parse2_to_tokens(quote! { <a> }, ParserConfig::new(), codegen);
+    assert_eq!(
+        tokens.to_string(),
+        quote! { compile_error! { "open tag has no corresponding close tag and is not self-closing" } }
+            .to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_name_matches() -> Result<()> {
+    let nodes = parse2(quote! { <div></div><ui::Button /><data-id /> })?;
+
+    assert!(get_element(&nodes, 0).name_matches("div"));
+    assert!(!get_element(&nodes, 0).name_matches("span"));
+    assert!(get_element(&nodes, 1).name_matches("ui::Button"));
+    assert!(get_element(&nodes, 2).name_matches("data-id"));
+
+    let error = parse2(quote! { <div></span> }).unwrap_err();
+    assert_eq!(error.to_string(), "close tag has no corresponding open tag");
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_events() -> Result<()> {
+    use syn::parse::{ParseStream, Parser as _};
+    use syn_rsx::{Event, Parser};
+
+    let parser = Parser::new(ParserConfig::new());
+    let events: Vec<_> = (|input: ParseStream| parser.parse_events(input))
+        .parse2(quote! { <div>"a"</div> })?
+        .collect();
+    assert_eq!(events.len(), 3);
+    let Event::OpenTag { name, attributes, .. } = &events[0] else { panic!("expected open tag") };
+    let Event::Text(text) = &events[1] else { panic!("expected text") };
+    let Event::CloseTag { name: close_name, .. } = &events[2] else { panic!("expected close tag") };
+    assert_eq!(name.to_string(), "div");
+    assert!(attributes.is_empty());
+    assert_eq!(String::try_from(&text.value)?, "a");
+    assert_eq!(close_name.to_string(), "div");
+
+    let tokens = quote! {
+        <div class="x">"a"<br /><>{b}</></div>
+        <!-- "c" -->
+    };
+    let events: Vec<_> = (|input: ParseStream| parser.parse_events(input))
+        .parse2(tokens)?
+        .map(|event| match event {
+            Event::OpenTag {
+                name, attributes, ..
+            } => {
+                format!("open {} {}", name, attributes.len())
+            }
+            Event::CloseTag { name, .. } => format!("close {}", name),
+            Event::OpenFragment(_) => "open fragment".to_owned(),
+            Event::CloseFragment(_) => "close fragment".to_owned(),
+            Event::Text(_) => "text".to_owned(),
+            Event::Comment(_) => "comment".to_owned(),
+            Event::Block(_) => "block".to_owned(),
+            Event::Doctype(_) => "doctype".to_owned(),
+        })
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            "open div 1",
+            "text",
+            "open br 0",
+            "close br",
+            "open fragment",
+            "block",
+            "close fragment",
+            "close div",
+            "comment",
+        ]
+    );
+
+    let error = (|input: ParseStream| parser.parse_events(input))
+        .parse2(quote! { <div> })
+        .err();
+    assert!(error.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_error_on_trailing_content() -> Result<()> {
+    use std::str::FromStr;
+
+    let config = || ParserConfig::new().error_on_trailing_content(true);
+    assert!(parse2_with_config(quote! { <html></html> }, config()).is_ok());
+    assert!(parse2_with_config(quote! { <!DOCTYPE html><html></html> }, config()).is_ok());
+    assert!(parse2_with_config(quote! { <html></html><!-- "end" --> }, config()).is_ok());
+
+    let tokens = proc_macro2::TokenStream::from_str(r#"<html></html> "stray""#).expect("tokens");
+    let error = parse2_with_config(tokens, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected content after 1 top level nodes"
+    );
+    assert_eq!(error.span().start().column, 14);
+
+    let config = config().number_of_top_level_nodes(2);
+    assert!(parse2_with_config(quote! { <!DOCTYPE html><html></html> }, config).is_ok());
+
+    let config = || ParserConfig::new().error_on_trailing_content(true);
+    let error =
+        parse2_with_config(quote! { <!DOCTYPE html><html></html><p></p> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected content after 1 top level nodes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_comment_text() -> Result<()> {
+    let nodes = parse2(quote! { <!-- "x" --><!-- 1 --> })?;
+
+    let Node::Comment(comment) = &nodes[0] else { panic!("expected comment") };
+    assert_eq!(comment.text(), "x");
+    let Node::Comment(comment) = &nodes[1] else { panic!("expected comment") };
+    assert_eq!(comment.text(), "1");
+
+    assert!(parse2(quote! { <!-- x --> }).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_case_insensitive_element_matching() -> Result<()> {
+    let config = || {
+        ParserConfig::new().element_classification(std::collections::HashMap::from([(
+            "script",
+            syn_rsx::ElementClassification::RawText,
+        )]))
+    };
+    let tokens = || quote! { <SCRIPT><div /></SCRIPT> };
+
+    assert!(parse2_with_config(tokens(), config()).is_ok());
+
+    let error =
+        parse2_with_config(tokens(), config().case_insensitive_element_matching(true)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "raw text element `SCRIPT` can only contain text"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_attributes_tokens() -> Result<()> {
+    let nodes = parse2(quote! { <div a="1" b c={d} {..props}>"child"</div> })?;
+    let attributes = get_element(&nodes, 0).attributes_tokens();
+
+    assert_eq!(
+        attributes.to_string(),
+        quote! { a="1" b c={d} {..props} }.to_string()
+    );
+
+    let reparsed = parse2(quote! { <Forwarded #attributes /> })?;
+    let original: Vec<_> = get_element(&nodes, 0)
+        .attributes
+        .iter()
+        .map(|attribute| attribute.to_string())
+        .collect();
+    let forwarded: Vec<_> = get_element(&reparsed, 0)
+        .attributes
+        .iter()
+        .map(|attribute| attribute.to_string())
+        .collect();
+    assert_eq!(original, forwarded);
+
+    Ok(())
+}
+
+#[test]
+fn test_respan_transformed_blocks() -> Result<()> {
+    use std::str::FromStr;
+
+    let tokens = || proc_macro2::TokenStream::from_str("<div>\n{%}</div>").expect("tokens");
+    let config = || {
+        ParserConfig::new().transform_block(|input| {
+            input.parse::<syn::Token![%]>()?;
+            Ok(Some(quote! { 1 2 }))
+        })
+    };
+
+    let error = parse2_with_config(tokens(), config()).unwrap_err();
+    assert_ne!(error.span().start().line, 2);
+
+    let error = parse2_with_config(tokens(), config().respan_transformed_blocks(true)).unwrap_err();
+    assert_eq!(error.span().start().line, 2);
+    assert_eq!(error.span().start().column, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_element_is_empty() -> Result<()> {
+    let nodes = parse2(quote! {
+        <div></div>
+        <div>" "</div>
+        <div>"x"</div>
+        <div><!-- "comment" --></div>
+        <div>{""}</div>
+    })?;
+
+    assert!(get_element(&nodes, 0).is_empty());
+    assert!(get_element(&nodes, 1).is_empty());
+    assert!(!get_element(&nodes, 2).is_empty());
+    assert!(!get_element(&nodes, 3).is_empty());
+    assert!(!get_element(&nodes, 4).is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "span-locations")]
+fn test_partition_diagnostics() -> Result<()> {
+    use syn_rsx::diagnostic::{partition, Level};
+
+    let config = || ParserConfig::new().enforce_naming_convention(true);
+
+    let (nodes, diagnostics) = syn_rsx::parse2_with_diagnostics(quote! { <Div /> }, config());
+    assert!(nodes.is_some());
+    let (errors, warnings) = partition(diagnostics);
+    assert!(errors.is_empty());
+    assert_eq!(warnings.len(), 1);
+
+    let (nodes, diagnostics) = syn_rsx::parse2_with_diagnostics(quote! { <Div /><div> }, config());
+    assert!(nodes.is_none());
+    assert_eq!(diagnostics.len(), 2);
+    let (errors, warnings) = partition(diagnostics);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].level, Level::Error);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].level, Level::Warning);
+
+    Ok(())
+}
+
+#[test]
+fn test_slots() -> Result<()> {
+    use syn_rsx::slots;
+
+    let tokens = quote! {
+        <template>
+            <slot name="header" />
+            <slot />
+        </template>
+        <my-card>
+            <div slot="header" />
+            <p slot="footer" />
+        </my-card>
+    };
+    let nodes = parse2(tokens)?;
+    let info = slots::collect(&nodes);
+
+    assert_eq!(info.slots.len(), 2);
+    assert_eq!(info.slots[0].name.as_deref(), Some("header"));
+    assert_eq!(info.slots[0].assigned.len(), 1);
+    assert_eq!(info.slots[0].assigned[0].name.to_string(), "div");
+    assert_eq!(info.slots[1].name, None);
+    assert!(info.slots[1].assigned.is_empty());
+    assert_eq!(info.unassigned.len(), 1);
+    assert_eq!(info.unassigned[0].name.to_string(), "p");
+
+    Ok(())
+}
+
+#[test]
+fn test_config_getters() -> Result<()> {
+    use syn_rsx::ElementClassification;
+
+    let classification = std::collections::HashMap::from([("br", ElementClassification::Void)]);
+    let config = ParserConfig::new()
+        .flat_tree()
+        .number_of_top_level_nodes(2)
+        .allowed_elements(Some(HashSet::from(["br"])))
+        .element_classification(classification.clone());
+
+    assert!(config.get_flat_tree());
+    assert_eq!(config.get_number_of_top_level_nodes(), Some(2));
+    assert_eq!(config.get_type_of_top_level_nodes(), None);
+    assert_eq!(config.get_allowed_elements(), Some(&HashSet::from(["br"])));
+    assert_eq!(config.get_element_classification(), &classification);
+    assert!(config.get_template_elements().is_empty());
+    assert!(!config.get_collect_stats());
+    assert!(config.get_call_site_span().is_none());
+    assert!(config.get_transform_block().is_none());
+
+    let config = ParserConfig::new()
+        .collect_stats(true)
+        .allow_unclosed_fragment(true)
+        .shorthand_attributes(true)
+        .error_on_trailing_content(true)
+        .validate_doctype_position(true)
+        .single_root_element(true)
+        .transform_text(|_| None);
+
+    assert!(config.get_collect_stats());
+    assert!(config.get_allow_unclosed_fragment());
+    assert!(config.get_shorthand_attributes());
+    assert!(!config.get_allow_attribute_negation());
+    assert!(config.get_error_on_trailing_content());
+    assert!(config.get_validate_doctype_position());
+    assert!(config.get_single_root_element());
+    assert!(config.get_transform_text().is_some());
+    assert!(config.get_default_attributes().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_static_subtrees() -> Result<()> {
+    use syn_rsx::analyze::static_subtrees;
+
+    let nodes = parse2(quote! { <div class="x"><span>"y"</span></div> })?;
+    let spans = static_subtrees(&nodes);
+    assert_eq!(spans.len(), 1);
+    assert_eq!(
+        format!("{:?}", spans[0]),
+        format!("{:?}", get_element(&nodes, 0).span)
+    );
+
+    assert!(static_subtrees(&parse2(quote! { <div>{x}</div> })?).is_empty());
+    assert!(static_subtrees(&parse2(quote! { <div {..props} /> })?).is_empty());
+    assert!(static_subtrees(&parse2(quote! { <div class={x} /> })?).is_empty());
+
+    let nodes = parse2(quote! { <div>{x}<p>"a"</p><><b /></></div> })?;
+    assert_eq!(static_subtrees(&nodes).len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_text_dedented() -> Result<()> {
+    let nodes = parse2(quote! {
+        <pre>
+            "
+            fn main() {
+                println!();
+            }
+            "
+        </pre>
+        <p>1</p>
+    })?;
+
+    let Node::Text(text) = get_element_child(&nodes, 0, 0) else { panic!("expected text") };
+    assert_eq!(
+        text.dedented().as_deref(),
+        Some("\nfn main() {\n    println!();\n}\n")
+    );
+    let Node::Text(text) = get_element_child(&nodes, 1, 0) else { panic!("expected text") };
+    assert_eq!(text.dedented(), None);
+
+    // Whitespace wider than a byte counts as one char of indent.
+    let nodes = parse2(quote! { <pre>"  a\n\u{a0}b\n\u{3000}\u{3000}c"</pre> })?;
+    let Node::Text(text) = get_element_child(&nodes, 0, 0) else { panic!("expected text") };
+    assert_eq!(text.dedented().as_deref(), Some(" a\nb\n\u{3000}c"));
+
+    Ok(())
+}
+
+#[test]
+fn test_colon_attribute_values() -> Result<()> {
+    let attributes = |nodes: &[Node]| -> Vec<(String, Option<String>)> {
+        get_element(nodes, 0)
+            .attributes
+            .iter()
+            .map(|attribute| {
+                let Node::Attribute(attribute) = attribute else { panic!("expected attribute") };
+                let value = attribute.value.as_ref().map(|value| {
+                    quote::ToTokens::to_token_stream(value).to_string()
+                });
+                (attribute.key.to_string(), value)
+            })
+            .collect()
+    };
+
+    let nodes = parse2(quote! { <ui:box on:click={x} /> })?;
+    assert_eq!(
+        attributes(&nodes),
+        vec![("on:click".to_owned(), Some("{ x }".to_owned()))]
+    );
+    assert!(parse2(quote! { <div width: 100 /> }).is_err());
+
+    let config = ParserConfig::new().colon_attribute_values(true);
+    let nodes = parse2_with_config(
+        quote! { <ui:box width: 100 max-width: {w} a::b=1 hidden /> },
+        config,
+    )?;
+    assert_eq!(get_element(&nodes, 0).name.to_string(), "ui:box");
+    assert_eq!(
+        attributes(&nodes),
+        vec![
+            ("width".to_owned(), Some("100".to_owned())),
+            ("max-width".to_owned(), Some("{ w }".to_owned())),
+            ("a::b".to_owned(), Some("1".to_owned())),
+            ("hidden".to_owned(), None),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_node_respanned() -> Result<()> {
+    use std::str::FromStr;
+
+    use syn::spanned::Spanned;
+
+    let tokens =
+        proc_macro2::TokenStream::from_str(r#"<div class="x">{value}</div>"#).expect("tokens");
+    let nodes = parse2(tokens)?;
+    let span = proc_macro2::TokenStream::from_str("\n\n  target")
+        .expect("tokens")
+        .into_iter()
+        .next()
+        .expect("token")
+        .span();
+
+    let respanned = nodes[0].respanned(span);
+    assert_eq!(respanned.to_string(), nodes[0].to_string());
+
+    let Node::Element(element) = &respanned else { panic!("expected element") };
+    assert_eq!(element.span.start().line, 3);
+    assert_eq!(element.name.span().start().line, 3);
+    assert_eq!(get_element(&nodes, 0).span.start().line, 1);
+
+    let Node::Attribute(attribute) = &element.attributes[0] else { panic!("expected attribute") };
+    let value = attribute.value.as_ref().expect("value");
+    let lines = [
+        attribute.span,
+        attribute.key.span(),
+        value.as_ref().span(),
+        element.children[0].span(),
+    ]
+    .map(|span| span.start().line);
+    assert_eq!(lines, [3, 3, 3, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_doctype_position() -> Result<()> {
+    let config = || ParserConfig::new().validate_doctype_position(true);
+
+    let tokens = quote! { <!-- "license" --><!DOCTYPE html><html></html> };
+    assert!(parse2_with_config(tokens, config()).is_ok());
+
+    let error = parse2_with_config(quote! { <div /><!DOCTYPE html> }, config()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "doctype needs to be at the start of the document"
+    );
+
+    assert!(parse2(quote! { <div /><!DOCTYPE html> }).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_entries() -> Result<()> {
+    use quote::ToTokens;
+    use syn_rsx::AttributeEntry;
+
+    let nodes = parse2(quote! { <div a="1" b {props} {..rest} /> })?;
+    let entries: Vec<_> = get_element(&nodes, 0)
+        .attribute_entries()
+        .map(|entry| match entry {
+            AttributeEntry::Keyed { key, value } => format!(
+                "keyed {} {:?}",
+                key,
+                value.map(|value| value.to_token_stream().to_string())
+            ),
+            AttributeEntry::Block(block) => format!("block {}", block),
+            AttributeEntry::Spread(expr) => {
+                format!("spread {}", expr.to_token_stream())
+            }
+        })
+        .collect();
+
+    assert_eq!(
+        entries,
+        vec![
+            "keyed a Some(\"\\\"1\\\"\")",
+            "keyed b None",
+            "block { props }",
+            "spread rest",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_text_child() -> Result<()> {
+    let tokens = quote! {
+        <script>"let x = 1;"</script>
+        <style></style>
+        <div>"text"</div>
+    };
+    let config = ParserConfig::new().element_classification(std::collections::HashMap::from([
+        ("script", syn_rsx::ElementClassification::RawText),
+        ("style", syn_rsx::ElementClassification::RawText),
+    ]));
+    let nodes = parse2_with_config(tokens, config)?;
+
+    let text = get_element(&nodes, 0)
+        .raw_text_child()
+        .expect("raw text child");
+    assert_eq!(String::try_from(&text.value)?, "let x = 1;");
+    assert!(get_element(&nodes, 1).raw_text_child().is_none());
+    assert!(get_element(&nodes, 2).raw_text_child().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_comma_separated_attribute_values() -> Result<()> {
+    use quote::ToTokens;
+
+    let config = ParserConfig::new().comma_separated_attribute_values(true);
+    let tokens = quote! { <div class=a, {b}, "c" id=x hidden /> };
+    let nodes = parse2_with_config(tokens.clone(), config)?;
+
+    let attribute = get_element_attribute(&nodes, 0, 0);
+    let values: Vec<_> = attribute
+        .value_list()
+        .expect("value list")
+        .iter()
+        .map(|value| value.to_token_stream().to_string())
+        .collect();
+    assert_eq!(values, vec!["a", "{ b }", "\"c\""]);
+    assert_eq!(
+        attribute.to_token_stream().to_string(),
+        quote! { class = a, {b}, "c" }.to_string()
+    );
+    assert!(get_element_attribute(&nodes, 0, 1).value_list().is_none());
+    assert_eq!(get_element(&nodes, 0).attributes.len(), 3);
+
+    assert!(parse2(quote! { <div class=a, b /> }).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_fragment_close_for_element() -> Result<()> {
+    let error = parse2(quote! { <div>"a"</> }).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected close tag `</div>`, found fragment close tag `</>`"
+    );
+
+    let error = parse2(quote! { <><ui::Card></></> }).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected close tag `</ui::Card>`, found fragment close tag `</>`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_to_formatted_tokens() -> Result<()> {
+    use syn_rsx::FormatConfig;
+
+    let nodes = parse2(quote! { <DIV class="" id="x"><>"a"<SPAN></SPAN></></DIV> })?;
+
+    assert_eq!(
+        nodes[0]
+            .to_formatted_tokens(&FormatConfig::new())
+            .to_string(),
+        nodes[0].to_token_stream().to_string()
+    );
+
+    let config = FormatConfig::new().lowercase_names(true);
+    assert_eq!(
+        nodes[0].to_formatted_tokens(&config).to_string(),
+        quote! { <div class="" id="x"><>"a"<span></span></></div> }.to_string()
+    );
+
+    let config = config.drop_empty_attributes(true).self_close_empty(true);
+    assert_eq!(
+        nodes[0].to_formatted_tokens(&config).to_string(),
+        quote! { <div id="x"><>"a"<span /></></div> }.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_replace_and_push_children() -> Result<()> {
+    let mut nodes = parse2(quote! { <ul><li>"a"</li></ul><>"x"</> })?;
+    let mut extra = parse2(quote! { <li>"b"</li><li>"c"</li> })?;
+
+    let Node::Element(list) = &mut nodes[0] else { panic!("expected element") };
+    list.push_child(extra.remove(0));
+    assert_eq!(
+        list.to_token_stream().to_string(),
+        quote! { <ul><li>"a"</li><li>"b"</li></ul> }.to_string()
+    );
+    let previous = list.replace_children(extra);
+    assert_eq!(previous.len(), 2);
+    assert_eq!(
+        list.to_token_stream().to_string(),
+        quote! { <ul><li>"c"</li></ul> }.to_string()
+    );
+
+    let Node::Fragment(fragment) = &mut nodes[1] else { panic!("expected fragment") };
+    fragment.push_child(previous.into_iter().next().expect("child"));
+    assert_eq!(
+        fragment.to_token_stream().to_string(),
+        quote! { <>"x"<li>"a"</li></> }.to_string()
+    );
+    assert_eq!(fragment.replace_children(vec![]).len(), 2);
+    assert!(fragment.children.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_is_fully_static() -> Result<()> {
+    use syn_rsx::analyze::is_fully_static;
+
+    let nodes = parse2(quote! {
+        <!DOCTYPE html>
+        <!-- "comment" -->
+        <html lang="en"><body><p class="x" hidden>"text" 1</p><></></body></html>
+    })?;
+    assert!(is_fully_static(&nodes));
+
+    for tokens in [
+        quote! { <div><p>{x}</p></div> },
+        quote! { <div><p {..props} /></div> },
+        quote! { <div><p {props} /></div> },
+        quote! { <div><{tag} /></div> },
+        quote! { <div><p class=x /></div> },
+        quote! { <>{x}</> },
+    ] {
+        assert!(!is_fully_static(&parse2(tokens)?));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_with_siblings() -> Result<()> {
+    let nodes = parse2(quote! {
+        <p>"a"<b />"c"</p>
+        <hr />
+    })?;
+
+    let name = |node: Option<&Node>| match node {
+        Some(Node::Element(element)) => element.name.to_string(),
+        Some(node) => node.to_string(),
+        None => "-".to_owned(),
+    };
+    let mut visited = vec![];
+    syn_rsx::with_siblings(&nodes, |previous, node, next| {
+        visited.push(format!(
+            "{} {} {}",
+            name(previous),
+            name(Some(node)),
+            name(next)
+        ));
+    });
+
+    assert_eq!(visited, vec!["- p hr", "- a b", "a b c", "b c -", "p hr -"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_value_is_braced() -> Result<()> {
+    let tokens = quote! { <div a={ complex.expr() } b=complex.expr() c="x" d='l: {} e /> };
+    let nodes = parse2(tokens.clone())?;
+
+    assert!(get_element_attribute(&nodes, 0, 0).value_is_braced());
+    assert!(!get_element_attribute(&nodes, 0, 1).value_is_braced());
+    assert!(!get_element_attribute(&nodes, 0, 2).value_is_braced());
+    assert!(!get_element_attribute(&nodes, 0, 3).value_is_braced());
+    assert!(!get_element_attribute(&nodes, 0, 4).value_is_braced());
+
+    assert_roundtrip(tokens);
+    let reparsed = nodes[0].reparse(&ParserConfig::new())?;
+    assert!(get_element_attribute(&[reparsed], 0, 0).value_is_braced());
+
+    Ok(())
+}
+
+#[test]
+fn test_tokens_to_html() -> Result<()> {
+    let void_set: HashSet<_> = vec!["br", "meta"].into_iter().collect();
+
+    let html = syn_rsx::tokens_to_html(
+        quote! {
+            <!DOCTYPE html>
+            <html>
+                <head><meta charset="utf-8" /></head>
+                <body class="x" hidden>"a"<br />"b"<!-- "c" --></body>
+            </html>
+        },
+        &void_set,
+    )?;
+    assert_eq!(
+        html,
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8"></head><body class="x" hidden>a<br>b<!-- c --></body></html>"#
+    );
+
+    let html = syn_rsx::tokens_to_html(
+        quote! { <p title="a\"><script>">"<b>&amp;</b>"</p> },
+        &void_set,
+    )?;
+    assert_eq!(
+        html,
+        "<p title=\"a&quot;&gt;&lt;script&gt;\">&lt;b&gt;&amp;amp;&lt;/b&gt;</p>"
+    );
+
+    let html = syn_rsx::tokens_to_html(quote! { <div a=1 b=1.5 c=true d='"' e=2u8 /> }, &void_set)?;
+    assert_eq!(
+        html,
+        r#"<div a="1" b="1.5" c="true" d="&quot;" e="2"></div>"#
+    );
+
+    let tokens = quote! { <div class=class>"a"{b}</div> };
+    let Err(syn_rsx::Error::Dynamic(error)) = syn_rsx::tokens_to_html(tokens, &void_set) else { panic!("expected dynamic error") };
+    assert_eq!(error.into_iter().count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_path_attribute_key() -> Result<()> {
+    let tokens = quote! { <Component a::b=1 c::d::e={2} f:g=3 /> };
+    let nodes = parse2(tokens.clone())?;
+
+    let Node::Attribute(NodeAttribute { key: syn_rsx::NodeName::Path(path), .. }) = &get_element(&nodes, 0).attributes[0] else { panic!("expected path key") };
+    assert_eq!(path.path.segments.len(), 2);
+    let Node::Attribute(NodeAttribute { key: syn_rsx::NodeName::Path(path), .. }) = &get_element(&nodes, 0).attributes[1] else { panic!("expected path key") };
+    assert_eq!(path.path.segments.len(), 3);
+    assert_eq!(get_element_attribute(&nodes, 0, 0).key.to_string(), "a::b");
+    assert_eq!(
+        get_element_attribute(&nodes, 0, 1).key.to_string(),
+        "c::d::e"
+    );
+    assert_eq!(get_element_attribute(&nodes, 0, 2).key.to_string(), "f:g");
+    assert_roundtrip(tokens);
+
+    let config = ParserConfig::new().colon_attribute_values(true);
+    let nodes = parse2_with_config(quote! { <Component a::b=1 c:2 /> }, config)?;
+    assert_eq!(get_element_attribute(&nodes, 0, 0).key.to_string(), "a::b");
+    assert_eq!(get_element_attribute(&nodes, 0, 1).key.to_string(), "c");
+
+    Ok(())
+}
+
+#[test]
+fn test_default_attributes() -> Result<()> {
+    let config = || {
+        ParserConfig::new().default_attributes(
+            vec![("button", vec![("type", "button"), ("aria-label", "x")])]
+                .into_iter()
+                .collect(),
+        )
+    };
+
+    let nodes = parse2_with_config(quote! { <div><button>"a"</button></div> }, config())?;
+    let button = get_element_child(&nodes, 0, 0);
+    assert_eq!(
+        button.to_string(),
+        r#"<button type="button" aria-label="x">a</button>"#
+    );
+
+    let nodes = parse2_with_config(quote! { <button type="submit" /> }, config())?;
+    assert_eq!(get_element(&nodes, 0).attributes.len(), 2);
+    assert_eq!(
+        nodes[0].to_string(),
+        r#"<button type="submit" aria-label="x"></button>"#
+    );
+
+    let allowed = config().allowed_attributes(Some(HashSet::from(["type"])));
+    let error = parse2_with_config(quote! { <button /> }, allowed).unwrap_err();
+    assert_eq!(error.to_string(), "attribute `aria-label` is not allowed");
+
+    let max = config().max_attributes_per_element(1);
+    let error = parse2_with_config(quote! { <button /> }, max).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "elements can't have more than 1 attributes"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "span-locations")]
+fn test_diagnostic_suggestions() {
+    use proc_macro2::LineColumn;
+    use syn_rsx::diagnostic::Suggestion;
+
+    let suggestion = |source: &str| {
+        let diagnostics = syn_rsx::parse_str(source).expect_err("error");
+        diagnostics[0].suggestion.clone()
+    };
+    let line_column = |column| LineColumn { line: 1, column };
+
+    assert_eq!(
+        suggestion("<div><p></div>"),
+        Some(Suggestion {
+            start: line_column(10),
+            end: line_column(13),
+            replacement: "p".to_owned(),
+        })
+    );
+    assert_eq!(
+        suggestion(r#"<div>"a""#),
+        Some(Suggestion {
+            start: line_column(8),
+            end: line_column(8),
+            replacement: "</div>".to_owned(),
+        })
+    );
+    assert_eq!(
+        syn_rsx::parse_str_with_config(
+            r#"<div> class="x"</div>"#,
+            ParserConfig::new().detect_misplaced_attributes(true)
+        )
+        .expect_err("error")[0]
+            .suggestion,
+        Some(Suggestion {
+            start: line_column(4),
+            end: line_column(15),
+            replacement: r#" class="x">"#.to_owned(),
+        })
+    );
+    assert_eq!(suggestion("<div></div></div>"), None);
+
+    let (nodes, diagnostics) =
+        syn_rsx::parse2_with_diagnostics(quote! { <a></b> }, ParserConfig::new());
+    assert!(nodes.is_none());
+    assert_eq!(
+        diagnostics[0]
+            .suggestion
+            .as_ref()
+            .map(|s| s.replacement.as_str()),
+        Some("a")
+    );
+}
+
+#[test]
+fn test_parse_in_expr_position() -> Result<()> {
+    use syn::{braced, parse::ParseStream, Stmt, Token};
+
+    let parser = |input: ParseStream| {
+        let content;
+        braced!(content in input);
+        let mut stmts: Vec<Stmt> = vec![];
+        while !content.peek(Token![<]) {
+            stmts.push(content.parse()?);
+        }
+        let nodes = syn_rsx::parse_in_expr_position(&content)?;
+        input.parse::<Token![;]>()?;
+
+        Ok((stmts, nodes))
+    };
+
+    let tokens = quote! {
+        {
+            let x = 1;
+            let y = x + 1;
+            <div>{x}</div>
+            <p>{y}</p>
+        };
+    };
+    let (stmts, nodes) = syn::parse::Parser::parse2(parser, tokens)?;
+    assert_eq!(stmts.len(), 2);
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(get_element(&nodes, 1).name.to_string(), "p");
+
+    Ok(())
+}
+
+#[test]
+fn test_warn_redundant_void_close() -> Result<()> {
+    use std::str::FromStr;
+
+    use syn_rsx::ElementClassification;
+
+    let config = || {
+        ParserConfig::new()
+            .element_classification(
+                vec![("br", ElementClassification::Void)]
+                    .into_iter()
+                    .collect(),
+            )
+            .warn_redundant_void_close(true)
+    };
+
+    let tokens = proc_macro2::TokenStream::from_str("<p><br></br><br/></p>").expect("tokens");
+    let (_, warnings) = syn_rsx::parse2_with_warnings(tokens, config())?;
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].message,
+        "unnecessary close tag of void element `br`"
+    );
+    #[cfg(feature = "span-locations")]
+    {
+        let diagnostic = syn_rsx::diagnostic::SimpleDiagnostic::from(&warnings[0]);
+        let suggestion = diagnostic.suggestion.expect("suggestion");
+        assert_eq!((suggestion.start.column, suggestion.end.column), (7, 12));
+        assert_eq!(suggestion.replacement, "");
+    }
+
+    let (_, warnings) = syn_rsx::parse2_with_warnings(quote! { <p><br /><br></p> }, config())?;
+    assert!(warnings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_interpolate_string_attributes() -> Result<()> {
+    use syn_rsx::TemplatePart;
+
+    let config = || ParserConfig::new().interpolate_string_attributes(true);
+    let tokens = quote! { <div title="Hi {name}!" class="{{a}} {b}" id="x" data=value /> };
+    let nodes = parse2_with_config(tokens.clone(), config())?;
+
+    let Some([TemplatePart::Literal(hi), TemplatePart::Expr(name), TemplatePart::Literal(bang)]) = get_element_attribute(&nodes, 0, 0).template() else { panic!("expected template") };
+    assert_eq!(
+        (hi.as_str(), name.to_string().as_str(), bang.as_str()),
+        ("Hi ", "name", "!")
+    );
+    assert_eq!(
+        get_element_attribute(&nodes, 0, 1).template(),
+        Some(
+            &[
+                TemplatePart::Literal("{a} ".to_owned()),
+                TemplatePart::Expr(syn::parse_quote! { b }),
+            ][..]
+        )
+    );
+    assert_eq!(
+        get_element_attribute(&nodes, 0, 2).template(),
+        Some(&[TemplatePart::Literal("x".to_owned())][..])
+    );
+    assert_eq!(get_element_attribute(&nodes, 0, 3).template(), None);
+    assert_eq!(
+        nodes[0].to_string(),
+        r#"<div title="Hi {name}!" class="{{a}} {b}" id="x" data={value}></div>"#
+    );
+
+    let nodes = parse2(tokens)?;
+    assert_eq!(get_element_attribute(&nodes, 0, 0).template(), None);
+
+    for invalid in [
+        quote! { <div a="{1}" /> },
+        quote! { <div a="{b" /> },
+        quote! { <div a="b}" /> },
+    ] {
+        assert!(parse2_with_config(invalid, config()).is_err());
+    }
+
+    Ok(())
+}
+
+fn get_element(nodes: &[Node], element_index: usize) -> &NodeElement {
+    let Some(Node::Element(element)) = nodes.get(element_index) else { panic!("expected element") };
+    element
+}
+
+fn get_element_attribute(
+    nodes: &[Node],
+    element_index: usize,
+    attribute_index: usize,
+) -> &NodeAttribute {
+    let Some(Node::Element(element)) =
+        nodes.get(element_index) else { panic!("expected element") };
+    let Some(Node::Attribute(attribute)) =
+        element.attributes.get(attribute_index) else { panic!("expected attribute") };
+
+    attribute
+}
+
+fn get_element_child(nodes: &[Node], element_index: usize, child_index: usize) -> &Node {
+    let Some(Node::Element(element)) = nodes.get(element_index) else { panic!("expected element") };
+    element.children.get(child_index).expect("child")
+}
+
+/// Assert that re-emitting the parsed nodes yields the same tokens, ignoring
+/// spans and spacing.
+fn assert_roundtrip(tokens: proc_macro2::TokenStream) {
+    let nodes = parse2(tokens.clone()).expect("tokens should parse");
+    let emitted = nodes
+        .iter()
+        .map(Node::to_token_stream)
+        .collect::<proc_macro2::TokenStream>();
+
+    assert!(
+        tokens_eq(tokens.clone(), emitted.clone()),
+        "tokens don't round-trip\n  parsed: {}\n emitted: {}",
+        tokens,
+        emitted
+    );
+}
+
+/// Compare token streams by delimiters, punctuation characters, identifiers
+/// and literals.
+fn tokens_eq(left: proc_macro2::TokenStream, right: proc_macro2::TokenStream) -> bool {
+    use proc_macro2::TokenTree;
+
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return true,
+            (Some(TokenTree::Group(left)), Some(TokenTree::Group(right))) => {
+                if left.delimiter() != right.delimiter()
+                    || !tokens_eq(left.stream(), right.stream())
+                {
+                    return false;
+                }
+            }
+            (Some(TokenTree::Punct(left)), Some(TokenTree::Punct(right))) => {
+                if left.as_char() != right.as_char() {
+                    return false;
+                }
+            }
+            (Some(TokenTree::Ident(left)), Some(TokenTree::Ident(right))) => {
+                if left != right {
+                    return false;
+                }
+            }
+            (Some(TokenTree::Literal(left)), Some(TokenTree::Literal(right))) => {
+                if left.to_string() != right.to_string() {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
 }